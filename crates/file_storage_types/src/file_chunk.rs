@@ -0,0 +1,51 @@
+use hdi::prelude::*;
+pub const MAX_CHUNK_BYTES: usize = 256 * 1024;
+// The raw bytes of a slice of a file, so that no entry gets close to the entry size limit
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct FileChunk(pub SerializedBytes);
+pub fn check_file_chunk(file_chunk: &FileChunk) -> Option<String> {
+    let size = file_chunk.0.bytes().len();
+    if size == 0 {
+        return Some(String::from("FileChunks cannot be empty"));
+    }
+    if size > MAX_CHUNK_BYTES {
+        return Some(
+            format!(
+                "FileChunk is {} bytes long, but at most {} are allowed", size,
+                MAX_CHUNK_BYTES
+            ),
+        );
+    }
+    None
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn chunk(size: usize) -> FileChunk {
+        FileChunk(SerializedBytes::from(UnsafeBytes::from(vec![0; size])))
+    }
+    #[test]
+    fn chunks_up_to_the_limit_are_accepted() {
+        assert_eq!(check_file_chunk(&chunk(1)), None);
+        assert_eq!(check_file_chunk(&chunk(MAX_CHUNK_BYTES)), None);
+    }
+    #[test]
+    fn empty_chunks_are_rejected() {
+        assert_eq!(
+            check_file_chunk(&chunk(0)),
+            Some("FileChunks cannot be empty".to_string())
+        );
+    }
+    #[test]
+    fn oversized_chunks_are_rejected() {
+        assert_eq!(
+            check_file_chunk(&chunk(MAX_CHUNK_BYTES + 1)),
+            Some(format!(
+                "FileChunk is {} bytes long, but at most {} are allowed",
+                MAX_CHUNK_BYTES + 1,
+                MAX_CHUNK_BYTES
+            ))
+        );
+    }
+}
@@ -0,0 +1,89 @@
+use hdi::prelude::*;
+pub const MAX_CHUNKS: usize = 1024;
+// Where FileManifests live in a DNA, so that other integrity zomes can recognise them by the
+// entry type of their actions
+pub const FILE_STORAGE_INTEGRITY_ZOME_NAME: &str = "file_storage_integrity";
+pub const FILE_MANIFEST_ENTRY_INDEX: u8 = 1;
+// Describes a file and lists, in order, the FileChunks that its bytes are split into
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct FileManifest {
+    pub name: String,
+    pub mime_type: String,
+    pub size: usize,
+    pub chunks: Vec<EntryHash>,
+}
+pub fn check_file_manifest_fields(file_manifest: &FileManifest) -> Option<String> {
+    if file_manifest.name.trim().is_empty() {
+        return Some(String::from("FileManifest name cannot be empty"));
+    }
+    let is_mime_type = file_manifest
+        .mime_type
+        .split_once('/')
+        .map(|(type_, subtype)| !type_.is_empty() && !subtype.is_empty())
+        .unwrap_or(false);
+    if !is_mime_type {
+        return Some(
+            format!("FileManifest mime type {:?} is not a valid MIME type", file_manifest
+            .mime_type),
+        );
+    }
+    if file_manifest.chunks.is_empty() {
+        return Some(String::from("FileManifest must list at least one chunk"));
+    }
+    if file_manifest.chunks.len() > MAX_CHUNKS {
+        return Some(
+            format!(
+                "FileManifest has {} chunks, but at most {} are allowed", file_manifest
+                .chunks.len(), MAX_CHUNKS
+            ),
+        );
+    }
+    None
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn manifest(name: &str, mime_type: &str, chunks: usize) -> FileManifest {
+        FileManifest {
+            name: name.to_string(),
+            mime_type: mime_type.to_string(),
+            size: 0,
+            chunks: (0..chunks)
+                .map(|i| EntryHash::from_raw_36(vec![i as u8; 36]))
+                .collect(),
+        }
+    }
+    #[test]
+    fn valid_manifest_passes() {
+        assert_eq!(
+            check_file_manifest_fields(&manifest("image.png", "image/png", 2)),
+            None
+        );
+    }
+    #[test]
+    fn empty_name_is_rejected() {
+        assert_eq!(
+            check_file_manifest_fields(&manifest(" ", "image/png", 1)),
+            Some("FileManifest name cannot be empty".to_string())
+        );
+    }
+    #[test]
+    fn malformed_mime_type_is_rejected() {
+        assert_eq!(
+            check_file_manifest_fields(&manifest("image.png", "png", 1)),
+            Some("FileManifest mime type \"png\" is not a valid MIME type".to_string())
+        );
+        assert_eq!(
+            check_file_manifest_fields(&manifest("image.png", "image/", 1)),
+            Some("FileManifest mime type \"image/\" is not a valid MIME type".to_string())
+        );
+    }
+    #[test]
+    fn manifest_without_chunks_is_rejected() {
+        assert_eq!(
+            check_file_manifest_fields(&manifest("empty.txt", "text/plain", 0)),
+            Some("FileManifest must list at least one chunk".to_string())
+        );
+    }
+}
@@ -0,0 +1,6 @@
+// The entries of the file_storage zome, without any of its callbacks, so that other integrity
+// zomes can read them without exporting file_storage's callbacks as their own
+pub mod file_manifest;
+pub use file_manifest::*;
+pub mod file_chunk;
+pub use file_chunk::*;
@@ -0,0 +1,321 @@
+use std::net::Ipv4Addr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{anyhow, Context, Result};
+use clap::{Parser, Subcommand, ValueEnum};
+use hdk::prelude::*;
+use holochain_client::{
+    AdminWebsocket, AppWebsocket, AuthorizeSigningCredentialsPayload, ClientAgentSigner,
+    IssueAppAuthenticationTokenPayload,
+};
+use posts_client::websocket::AppWebsocketCaller;
+use posts_client::{
+    Certificate, CertificateType, ImportContentInput, ListOptions, Post, PostsClient,
+};
+
+mod portable;
+mod views;
+use portable::*;
+use views::*;
+
+#[derive(Parser, Debug)]
+#[command(name = "posts-cli", about = "Operate on a posts cell of a local conductor")]
+struct Cli {
+    /// Port of the conductor's admin interface
+    #[arg(long, env = "POSTS_ADMIN_PORT")]
+    admin_port: u16,
+    /// Port of the conductor's app interface
+    #[arg(long, env = "POSTS_APP_PORT")]
+    app_port: u16,
+    #[arg(long, default_value = "posts_test")]
+    app_id: String,
+    #[arg(long, default_value = "posts_test")]
+    role_name: String,
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// List all posts, at their latest revision, as JSON lines
+    List {
+        #[arg(long)]
+        exclude_moderated: bool,
+    },
+    /// Show a post with its revisions, comments and certificates
+    Show {
+        #[arg(value_parser = parse_action_hash)]
+        post_hash: ActionHash,
+    },
+    /// Create the post, or list of posts, in a JSON or YAML file
+    Create { file: PathBuf },
+    /// Certify an agent for a post
+    Certify {
+        #[arg(long, value_parser = parse_action_hash)]
+        post: ActionHash,
+        #[arg(long, value_parser = parse_agent_pub_key)]
+        agent: AgentPubKey,
+        /// Entry hash of a certificate that this one builds on, can be repeated
+        #[arg(long = "certification", value_parser = parse_entry_hash)]
+        certifications: Vec<EntryHash>,
+        #[arg(long, value_enum, default_value_t = CertificateTypeArg::One)]
+        certificate_type: CertificateTypeArg,
+    },
+    /// Print the signals emitted by the posts zome as JSON lines until interrupted
+    TailSignals,
+    /// Export every post revision, comment, like and certificate authored by this agent
+    Export {
+        output: PathBuf,
+        #[arg(long, value_enum)]
+        format: Option<Format>,
+    },
+    /// Recreate exported content in this cell, remapping references to the new hashes
+    Import {
+        file: PathBuf,
+        #[arg(long, value_enum)]
+        format: Option<Format>,
+        /// Number of records imported per zome call
+        #[arg(long, default_value_t = 50)]
+        batch_size: usize,
+        /// Seconds to wait before retrying once the rate limit is reached
+        #[arg(long, default_value_t = 60)]
+        rate_limit_wait_secs: u64,
+    },
+}
+
+#[derive(ValueEnum, Clone, Debug)]
+enum CertificateTypeArg {
+    One,
+    Two,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+enum PostsFile {
+    One(Post),
+    Many(Vec<Post>),
+}
+
+fn parse_action_hash(hash: &str) -> Result<ActionHash, String> {
+    ActionHashB64::from_b64_str(hash).map(Into::into).map_err(|e| e.to_string())
+}
+
+fn parse_entry_hash(hash: &str) -> Result<EntryHash, String> {
+    EntryHashB64::from_b64_str(hash).map(Into::into).map_err(|e| e.to_string())
+}
+
+fn parse_agent_pub_key(key: &str) -> Result<AgentPubKey, String> {
+    AgentPubKeyB64::from_b64_str(key).map(Into::into).map_err(|e| e.to_string())
+}
+
+fn read_posts(file: &Path) -> Result<Vec<Post>> {
+    let contents = std::fs::read_to_string(file).with_context(|| format!("Could not read {}", file.display()))?;
+    let posts = match file.extension().and_then(|extension| extension.to_str()) {
+        Some("yaml") | Some("yml") => serde_yaml::from_str(&contents)?,
+        _ => serde_json::from_str(&contents)?,
+    };
+    Ok(match posts {
+        PostsFile::One(post) => vec![post],
+        PostsFile::Many(posts) => posts,
+    })
+}
+
+fn print_json<T: Serialize>(value: &T) -> Result<()> {
+    println!("{}", serde_json::to_string(value)?);
+    Ok(())
+}
+
+// Connects to the app interface, authorizing a fresh signing key for the posts cell
+// through the admin interface
+async fn connect(cli: &Cli) -> Result<(PostsClient<AppWebsocketCaller>, CellId)> {
+    let admin_ws = AdminWebsocket::connect((Ipv4Addr::LOCALHOST, cli.admin_port))
+        .await
+        .map_err(|e| anyhow!("Could not connect to the admin interface: {:?}", e))?;
+    let issued = admin_ws
+        .issue_app_auth_token(IssueAppAuthenticationTokenPayload::for_installed_app_id(cli.app_id.clone()))
+        .await
+        .map_err(|e| anyhow!("Could not issue an app authentication token: {:?}", e))?;
+
+    let signer = ClientAgentSigner::default();
+    let app_ws = AppWebsocket::connect((Ipv4Addr::LOCALHOST, cli.app_port), issued.token, Arc::new(signer.clone()))
+        .await
+        .map_err(|e| anyhow!("Could not connect to the app interface: {:?}", e))?;
+
+    let app_info = app_ws
+        .app_info()
+        .await
+        .map_err(|e| anyhow!("Could not get the app info: {:?}", e))?
+        .ok_or(anyhow!("App {} is not installed", cli.app_id))?;
+    let cell_id = app_info
+        .cell_info
+        .get(&cli.role_name)
+        .and_then(|cells| {
+            cells.iter().find_map(|cell| match cell {
+                CellInfo::Provisioned(cell) => Some(cell.cell_id.clone()),
+                _ => None,
+            })
+        })
+        .ok_or(anyhow!("App {} has no provisioned cell for role {}", cli.app_id, cli.role_name))?;
+
+    let credentials = admin_ws
+        .authorize_signing_credentials(AuthorizeSigningCredentialsPayload {
+            cell_id: cell_id.clone(),
+            functions: None,
+        })
+        .await
+        .map_err(|e| anyhow!("Could not authorize signing credentials: {:?}", e))?;
+    signer.add_credentials(cell_id.clone(), credentials);
+
+    Ok((PostsClient::websocket(app_ws, cell_id.clone()), cell_id))
+}
+
+async fn latest_post(client: &PostsClient<AppWebsocketCaller>, post_hash: ActionHash) -> Result<Record> {
+    client
+        .get_latest_post(post_hash.clone())
+        .await?
+        .ok_or(anyhow!("Post {} was not found", post_hash))
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cli = Cli::parse();
+    let (client, cell_id) = connect(&cli).await?;
+
+    match cli.command {
+        Command::List { exclude_moderated } => {
+            let links = client.get_all_posts_with_options(ListOptions { exclude_moderated }).await?;
+            for link in links {
+                let Some(post_hash) = link.target.into_action_hash() else {
+                    continue;
+                };
+                print_json(&post_view(&latest_post(&client, post_hash).await?)?)?;
+            }
+        }
+        Command::Show { post_hash } => {
+            let original = client
+                .get_original_post(post_hash.clone())
+                .await?
+                .ok_or(anyhow!("Post {} was not found", post_hash))?;
+            let revisions = client.get_all_revisions_for_post(post_hash.clone()).await?;
+
+            let mut comments = vec![];
+            for link in client.get_comments_for_post(post_hash.clone()).await? {
+                let Some(comment_hash) = link.target.into_action_hash() else {
+                    continue;
+                };
+                if let Some(record) = client.get_latest_comment(comment_hash).await? {
+                    comments.push(comment_view(&record)?);
+                }
+            }
+
+            let mut certificates = vec![];
+            for link in client.get_certificates_for_post(post_hash.clone()).await? {
+                let Some(certificate_hash) = link.target.into_action_hash() else {
+                    continue;
+                };
+                if let Some(record) = client.get_certificate(certificate_hash).await? {
+                    certificates.push(certificate_view(&record)?);
+                }
+            }
+
+            print_json(&PostDetails {
+                original: post_view(&original)?,
+                revisions: revisions.iter().map(post_view).collect::<Result<_>>()?,
+                comments,
+                certificates,
+            })?;
+        }
+        Command::Create { file } => {
+            for post in read_posts(&file)? {
+                print_json(&post_view(&client.create_post(post).await?)?)?;
+            }
+        }
+        Command::Certify {
+            post,
+            agent,
+            certifications,
+            certificate_type,
+        } => {
+            let record = client
+                .create_certificate(Certificate {
+                    post_hash: post,
+                    agent,
+                    certifications_hashes: certifications,
+                    certificate_type: match certificate_type {
+                        CertificateTypeArg::One => CertificateType::TypeOne,
+                        CertificateTypeArg::Two => CertificateType::TypeTwo,
+                    },
+                    dna_hash: cell_id.dna_hash().clone(),
+                })
+                .await?;
+            print_json(&certificate_view(&record)?)?;
+        }
+        Command::TailSignals => {
+            let _ = client
+                .caller()
+                .app_websocket()
+                .on_signal(|signal| {
+                    let holochain_client::Signal::App { zome_name, signal, .. } = signal else {
+                        return;
+                    };
+                    if zome_name.0 != posts_client::POSTS_ZOME_NAME {
+                        return;
+                    }
+                    match signal.into_inner().decode::<posts_client::Signal>() {
+                        Ok(signal) => match serde_json::to_string(&signal) {
+                            Ok(line) => println!("{}", line),
+                            Err(e) => eprintln!("Could not serialize signal: {}", e),
+                        },
+                        Err(e) => eprintln!("Could not decode signal: {:?}", e),
+                    }
+                })
+                .await;
+            tokio::signal::ctrl_c().await?;
+        }
+        Command::Export { output, format } => {
+            let records = client.export_my_content().await?;
+            write_records(&output, Format::for_file(&output, format), &records)?;
+            eprintln!("Exported {} records to {}", records.len(), output.display());
+        }
+        Command::Import {
+            file,
+            format,
+            batch_size,
+            rate_limit_wait_secs,
+        } => {
+            let mut records = read_records(&file, Format::for_file(&file, format))?;
+            let mut previously_imported = vec![];
+            while !records.is_empty() {
+                let batch: Vec<_> = records.drain(..batch_size.max(1).min(records.len())).collect();
+                let result = client
+                    .import_content(ImportContentInput {
+                        records: batch.clone(),
+                        previously_imported: previously_imported.clone(),
+                    })
+                    .await?;
+                for imported in result.imported.iter() {
+                    print_json(&ImportedView {
+                        exported_action_hash: imported.exported_action_hash.clone().into(),
+                        action_hash: imported.action_hash.clone().into(),
+                    })?;
+                }
+                for not_imported in result.not_imported {
+                    eprintln!("Skipped {}: {}", not_imported.exported_action_hash, not_imported.reason);
+                }
+                previously_imported.extend(result.imported);
+                if result.remaining > 0 {
+                    records.splice(0..0, batch[batch.len() - result.remaining..].iter().cloned());
+                    eprintln!(
+                        "Rate limit reached, waiting {} seconds to import the remaining {} records",
+                        rate_limit_wait_secs,
+                        records.len()
+                    );
+                    tokio::time::sleep(std::time::Duration::from_secs(rate_limit_wait_secs)).await;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use clap::ValueEnum;
+use posts_client::ExportedRecord;
+
+#[derive(ValueEnum, Clone, Copy, Debug)]
+pub enum Format {
+    /// One JSON encoded record per line
+    Jsonl,
+    /// A CBOR encoded array of records
+    Cbor,
+}
+
+impl Format {
+    // Defaults to JSON Lines, unless the file has a .cbor extension
+    pub fn for_file(file: &Path, format: Option<Format>) -> Format {
+        format.unwrap_or(match file.extension().and_then(|extension| extension.to_str()) {
+            Some("cbor") => Format::Cbor,
+            _ => Format::Jsonl,
+        })
+    }
+}
+
+pub fn write_records(file: &Path, format: Format, records: &[ExportedRecord]) -> Result<()> {
+    let mut writer = BufWriter::new(
+        std::fs::File::create(file).with_context(|| format!("Could not create {}", file.display()))?,
+    );
+    match format {
+        Format::Jsonl => {
+            for record in records {
+                serde_json::to_writer(&mut writer, record)?;
+                writeln!(writer)?;
+            }
+        }
+        Format::Cbor => ciborium::into_writer(records, &mut writer)?,
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+pub fn read_records(file: &Path, format: Format) -> Result<Vec<ExportedRecord>> {
+    let reader = BufReader::new(
+        std::fs::File::open(file).with_context(|| format!("Could not open {}", file.display()))?,
+    );
+    match format {
+        Format::Jsonl => {
+            let mut records = vec![];
+            for (index, line) in reader.lines().enumerate() {
+                let line = line?;
+                if line.trim().is_empty() {
+                    continue;
+                }
+                records.push(
+                    serde_json::from_str(&line)
+                        .with_context(|| format!("Invalid record on line {}", index + 1))?,
+                );
+            }
+            Ok(records)
+        }
+        Format::Cbor => Ok(ciborium::from_reader(reader)?),
+    }
+}
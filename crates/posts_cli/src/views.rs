@@ -0,0 +1,89 @@
+use anyhow::{anyhow, Result};
+use hdk::prelude::*;
+use posts_client::{Certificate, CertificateType, Comment, Post};
+
+// Records as printed by the CLI, with base64 encoded hashes instead of raw bytes
+#[derive(Serialize, Debug)]
+pub struct RecordView<T> {
+    pub hash: ActionHashB64,
+    pub author: AgentPubKeyB64,
+    pub timestamp: Timestamp,
+    pub entry: T,
+}
+
+impl<T> RecordView<T> {
+    fn new(record: &Record, entry: T) -> Self {
+        Self {
+            hash: record.action_address().clone().into(),
+            author: record.action().author().clone().into(),
+            timestamp: record.action().timestamp(),
+            entry,
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub struct CommentView {
+    pub post_hash: ActionHashB64,
+}
+
+#[derive(Serialize, Debug)]
+pub struct CertificateView {
+    pub post_hash: ActionHashB64,
+    pub agent: AgentPubKeyB64,
+    pub certifications_hashes: Vec<EntryHashB64>,
+    pub certificate_type: CertificateType,
+}
+
+#[derive(Serialize, Debug)]
+pub struct ImportedView {
+    pub exported_action_hash: ActionHashB64,
+    pub action_hash: ActionHashB64,
+}
+
+#[derive(Serialize, Debug)]
+pub struct PostDetails {
+    pub original: RecordView<Post>,
+    pub revisions: Vec<RecordView<Post>>,
+    pub comments: Vec<RecordView<CommentView>>,
+    pub certificates: Vec<RecordView<CertificateView>>,
+}
+
+fn decode<T: TryFrom<SerializedBytes, Error = SerializedBytesError>>(record: &Record) -> Result<T> {
+    record
+        .entry()
+        .to_app_option()
+        .map_err(|e| anyhow!("Could not decode the entry of {}: {:?}", record.action_address(), e))?
+        .ok_or(anyhow!("Record {} has no entry", record.action_address()))
+}
+
+pub fn post_view(record: &Record) -> Result<RecordView<Post>> {
+    Ok(RecordView::new(record, decode::<Post>(record)?))
+}
+
+pub fn comment_view(record: &Record) -> Result<RecordView<CommentView>> {
+    let comment: Comment = decode(record)?;
+    Ok(RecordView::new(
+        record,
+        CommentView {
+            post_hash: comment.post_hash.into(),
+        },
+    ))
+}
+
+pub fn certificate_view(record: &Record) -> Result<RecordView<CertificateView>> {
+    let certificate: Certificate = decode(record)?;
+    Ok(RecordView::new(
+        record,
+        CertificateView {
+            post_hash: certificate.post_hash.into(),
+            agent: certificate.agent.into(),
+            certifications_hashes: certificate
+                .certifications_hashes
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+            certificate_type: certificate.certificate_type,
+        },
+    ))
+}
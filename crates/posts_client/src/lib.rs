@@ -0,0 +1,419 @@
+// Typed client for the posts coordinator zome, so that services and tests call its externs
+// with checked function names and input and output types instead of `conductor.call`
+use std::fmt::Debug;
+use std::future::Future;
+
+use hdk::prelude::*;
+
+#[cfg(feature = "sweettest")]
+pub mod sweettest;
+#[cfg(feature = "websocket")]
+pub mod websocket;
+
+pub use posts::channel::{AddPostToChannelInput, ChannelPostInput};
+pub use posts::comment::{GetCommentsForPostInput, UpdateCommentInput};
+pub use posts::moderation_action::ListOptions;
+pub use posts::notification::NotificationWithStatus;
+pub use posts::pagination::Page;
+pub use posts::portable_content::{
+    ExportedContent, ExportedRecord, ImportContentInput, ImportResult, ImportedRecord,
+    NotImportedRecord,
+};
+pub use posts::post::UpdatePostInput;
+pub use posts::post_draft::UpdateDraftInput;
+pub use posts::report::ReportedItem;
+pub use posts::scheduled_post::SchedulePostInput;
+pub use posts::Signal;
+pub use posts_integrity::*;
+
+pub const POSTS_ZOME_NAME: &str = "posts";
+
+#[derive(Debug)]
+pub enum PostsClientError {
+    Serialization(String),
+    Call(String),
+}
+
+impl std::fmt::Display for PostsClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PostsClientError::Serialization(e) => write!(f, "Could not serialize the zome call: {}", e),
+            PostsClientError::Call(e) => write!(f, "The zome call failed: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for PostsClientError {}
+
+pub type PostsClientResult<T> = Result<T, PostsClientError>;
+
+// A way of calling a function of the posts zome in a given cell
+pub trait ZomeCaller {
+    fn call<I, O>(&self, fn_name: &str, payload: I) -> impl Future<Output = PostsClientResult<O>> + Send
+    where
+        I: Serialize + Debug + Send,
+        O: DeserializeOwned + Debug + Send;
+}
+
+// The lifecycle callbacks (`init`, `post_commit`, `recv_remote_signal` and
+// `publish_scheduled_posts`) are called by the conductor, so they are not wrapped
+pub struct PostsClient<C> {
+    caller: C,
+}
+
+impl<C: ZomeCaller> PostsClient<C> {
+    pub fn new(caller: C) -> Self {
+        Self { caller }
+    }
+
+    pub fn caller(&self) -> &C {
+        &self.caller
+    }
+
+    // Posts
+
+    pub async fn create_post(&self, post: Post) -> PostsClientResult<Record> {
+        self.caller.call("create_post", post).await
+    }
+
+    pub async fn get_latest_post(&self, original_post_hash: ActionHash) -> PostsClientResult<Option<Record>> {
+        self.caller.call("get_latest_post", original_post_hash).await
+    }
+
+    pub async fn get_original_post(&self, original_post_hash: ActionHash) -> PostsClientResult<Option<Record>> {
+        self.caller.call("get_original_post", original_post_hash).await
+    }
+
+    pub async fn get_all_revisions_for_post(&self, original_post_hash: ActionHash) -> PostsClientResult<Vec<Record>> {
+        self.caller.call("get_all_revisions_for_post", original_post_hash).await
+    }
+
+    pub async fn update_post(&self, input: UpdatePostInput) -> PostsClientResult<Record> {
+        self.caller.call("update_post", input).await
+    }
+
+    pub async fn delete_post(&self, original_post_hash: ActionHash) -> PostsClientResult<ActionHash> {
+        self.caller.call("delete_post", original_post_hash).await
+    }
+
+    pub async fn get_all_deletes_for_post(
+        &self,
+        original_post_hash: ActionHash,
+    ) -> PostsClientResult<Option<Vec<SignedActionHashed>>> {
+        self.caller.call("get_all_deletes_for_post", original_post_hash).await
+    }
+
+    pub async fn get_oldest_delete_for_post(
+        &self,
+        original_post_hash: ActionHash,
+    ) -> PostsClientResult<Option<SignedActionHashed>> {
+        self.caller.call("get_oldest_delete_for_post", original_post_hash).await
+    }
+
+    pub async fn get_all_posts(&self) -> PostsClientResult<Vec<Link>> {
+        self.caller.call("get_all_posts", ()).await
+    }
+
+    pub async fn get_all_posts_with_options(&self, options: ListOptions) -> PostsClientResult<Vec<Link>> {
+        self.caller.call("get_all_posts_with_options", options).await
+    }
+
+    pub async fn get_all_posts_entry_hash(&self) -> PostsClientResult<Vec<Link>> {
+        self.caller.call("get_all_posts_entry_hash", ()).await
+    }
+
+    pub async fn get_posts_by_author(&self, author: AgentPubKey) -> PostsClientResult<Vec<Link>> {
+        self.caller.call("get_posts_by_author", author).await
+    }
+
+    pub async fn get_posts_by_author_entry_hash(&self, author: AgentPubKey) -> PostsClientResult<Vec<Link>> {
+        self.caller.call("get_posts_by_author_entry_hash", author).await
+    }
+
+    // Comments
+
+    pub async fn create_comment(&self, comment: Comment) -> PostsClientResult<Record> {
+        self.caller.call("create_comment", comment).await
+    }
+
+    pub async fn get_original_comment(&self, original_comment_hash: ActionHash) -> PostsClientResult<Option<Record>> {
+        self.caller.call("get_original_comment", original_comment_hash).await
+    }
+
+    pub async fn get_latest_comment(&self, original_comment_hash: ActionHash) -> PostsClientResult<Option<Record>> {
+        self.caller.call("get_latest_comment", original_comment_hash).await
+    }
+
+    pub async fn get_all_revisions_for_comment(
+        &self,
+        original_comment_hash: ActionHash,
+    ) -> PostsClientResult<Vec<Record>> {
+        self.caller.call("get_all_revisions_for_comment", original_comment_hash).await
+    }
+
+    pub async fn update_comment(&self, input: UpdateCommentInput) -> PostsClientResult<Record> {
+        self.caller.call("update_comment", input).await
+    }
+
+    pub async fn delete_comment(&self, original_comment_hash: ActionHash) -> PostsClientResult<ActionHash> {
+        self.caller.call("delete_comment", original_comment_hash).await
+    }
+
+    pub async fn get_all_deletes_for_comment(
+        &self,
+        original_comment_hash: ActionHash,
+    ) -> PostsClientResult<Option<Vec<SignedActionHashed>>> {
+        self.caller.call("get_all_deletes_for_comment", original_comment_hash).await
+    }
+
+    pub async fn get_oldest_delete_for_comment(
+        &self,
+        original_comment_hash: ActionHash,
+    ) -> PostsClientResult<Option<SignedActionHashed>> {
+        self.caller.call("get_oldest_delete_for_comment", original_comment_hash).await
+    }
+
+    pub async fn get_comments_for_post(&self, post_hash: ActionHash) -> PostsClientResult<Vec<Link>> {
+        self.caller.call("get_comments_for_post", post_hash).await
+    }
+
+    pub async fn get_comments_for_post_with_options(
+        &self,
+        input: GetCommentsForPostInput,
+    ) -> PostsClientResult<Vec<Link>> {
+        self.caller.call("get_comments_for_post_with_options", input).await
+    }
+
+    pub async fn get_deleted_comments_for_post(
+        &self,
+        post_hash: ActionHash,
+    ) -> PostsClientResult<Vec<(SignedActionHashed, Vec<SignedActionHashed>)>> {
+        self.caller.call("get_deleted_comments_for_post", post_hash).await
+    }
+
+    // Likes
+
+    pub async fn create_like(&self, like: Like) -> PostsClientResult<Record> {
+        self.caller.call("create_like", like).await
+    }
+
+    pub async fn get_like(&self, like_hash: ActionHash) -> PostsClientResult<Option<Record>> {
+        self.caller.call("get_like", like_hash).await
+    }
+
+    pub async fn delete_like(&self, original_like_hash: ActionHash) -> PostsClientResult<ActionHash> {
+        self.caller.call("delete_like", original_like_hash).await
+    }
+
+    pub async fn get_all_deletes_for_like(
+        &self,
+        original_like_hash: ActionHash,
+    ) -> PostsClientResult<Option<Vec<SignedActionHashed>>> {
+        self.caller.call("get_all_deletes_for_like", original_like_hash).await
+    }
+
+    pub async fn get_oldest_delete_for_like(
+        &self,
+        original_like_hash: ActionHash,
+    ) -> PostsClientResult<Option<SignedActionHashed>> {
+        self.caller.call("get_oldest_delete_for_like", original_like_hash).await
+    }
+
+    pub async fn get_likes_for_like(&self, like_hash: ActionHash) -> PostsClientResult<Vec<Link>> {
+        self.caller.call("get_likes_for_like", like_hash).await
+    }
+
+    pub async fn get_deleted_likes_for_like(
+        &self,
+        like_hash: ActionHash,
+    ) -> PostsClientResult<Vec<(SignedActionHashed, Vec<SignedActionHashed>)>> {
+        self.caller.call("get_deleted_likes_for_like", like_hash).await
+    }
+
+    // Certificates
+
+    pub async fn create_certificate(&self, certificate: Certificate) -> PostsClientResult<Record> {
+        self.caller.call("create_certificate", certificate).await
+    }
+
+    pub async fn get_certificate(&self, certificate_hash: ActionHash) -> PostsClientResult<Option<Record>> {
+        self.caller.call("get_certificate", certificate_hash).await
+    }
+
+    pub async fn get_certificates_for_post(&self, post_hash: ActionHash) -> PostsClientResult<Vec<Link>> {
+        self.caller.call("get_certificates_for_post", post_hash).await
+    }
+
+    pub async fn get_certificates_for_certified(&self, certified: AgentPubKey) -> PostsClientResult<Vec<Link>> {
+        self.caller.call("get_certificates_for_certified", certified).await
+    }
+
+    pub async fn get_certificates_for_certificate(
+        &self,
+        certificate_hash: EntryHash,
+    ) -> PostsClientResult<Vec<Link>> {
+        self.caller.call("get_certificates_for_certificate", certificate_hash).await
+    }
+
+    // Moderation and reports
+
+    pub async fn create_moderation_action(&self, moderation_action: ModerationAction) -> PostsClientResult<Record> {
+        self.caller.call("create_moderation_action", moderation_action).await
+    }
+
+    pub async fn get_moderation_actions_for(&self, target_hash: ActionHash) -> PostsClientResult<Vec<Link>> {
+        self.caller.call("get_moderation_actions_for", target_hash).await
+    }
+
+    pub async fn create_report(&self, report: Report) -> PostsClientResult<Record> {
+        self.caller.call("create_report", report).await
+    }
+
+    pub async fn get_reports_for(&self, target_hash: ActionHash) -> PostsClientResult<Vec<Link>> {
+        self.caller.call("get_reports_for", target_hash).await
+    }
+
+    pub async fn get_most_reported(&self, page: Page) -> PostsClientResult<Vec<ReportedItem>> {
+        self.caller.call("get_most_reported", page).await
+    }
+
+    // Block list
+
+    pub async fn get_block_list(&self) -> PostsClientResult<BlockList> {
+        self.caller.call("get_block_list", ()).await
+    }
+
+    pub async fn block_agent(&self, agent: AgentPubKey) -> PostsClientResult<BlockList> {
+        self.caller.call("block_agent", agent).await
+    }
+
+    pub async fn unblock_agent(&self, agent: AgentPubKey) -> PostsClientResult<BlockList> {
+        self.caller.call("unblock_agent", agent).await
+    }
+
+    pub async fn mute_agent(&self, agent: AgentPubKey) -> PostsClientResult<BlockList> {
+        self.caller.call("mute_agent", agent).await
+    }
+
+    pub async fn unmute_agent(&self, agent: AgentPubKey) -> PostsClientResult<BlockList> {
+        self.caller.call("unmute_agent", agent).await
+    }
+
+    // Drafts and scheduled posts
+
+    pub async fn save_draft(&self, post: Post) -> PostsClientResult<Record> {
+        self.caller.call("save_draft", post).await
+    }
+
+    pub async fn list_drafts(&self) -> PostsClientResult<Vec<Record>> {
+        self.caller.call("list_drafts", ()).await
+    }
+
+    pub async fn update_draft(&self, input: UpdateDraftInput) -> PostsClientResult<Record> {
+        self.caller.call("update_draft", input).await
+    }
+
+    pub async fn publish_draft(&self, draft_hash: ActionHash) -> PostsClientResult<Record> {
+        self.caller.call("publish_draft", draft_hash).await
+    }
+
+    pub async fn schedule_post(&self, input: SchedulePostInput) -> PostsClientResult<Record> {
+        self.caller.call("schedule_post", input).await
+    }
+
+    pub async fn get_scheduled_posts(&self) -> PostsClientResult<Vec<Record>> {
+        self.caller.call("get_scheduled_posts", ()).await
+    }
+
+    pub async fn cancel_scheduled_post(&self, scheduled_post_hash: ActionHash) -> PostsClientResult<ActionHash> {
+        self.caller.call("cancel_scheduled_post", scheduled_post_hash).await
+    }
+
+    pub async fn publish_scheduled_post(&self, scheduled_post_hash: ActionHash) -> PostsClientResult<Record> {
+        self.caller.call("publish_scheduled_post", scheduled_post_hash).await
+    }
+
+    // Notifications and subscriptions
+
+    pub async fn get_notifications(&self, page: Page) -> PostsClientResult<Vec<NotificationWithStatus>> {
+        self.caller.call("get_notifications", page).await
+    }
+
+    pub async fn mark_notifications_read(&self, notification_hashes: Vec<ActionHash>) -> PostsClientResult<()> {
+        self.caller.call("mark_notifications_read", notification_hashes).await
+    }
+
+    pub async fn get_unread_count(&self) -> PostsClientResult<usize> {
+        self.caller.call("get_unread_count", ()).await
+    }
+
+    pub async fn get_subscription(&self) -> PostsClientResult<Option<Subscription>> {
+        self.caller.call("get_subscription", ()).await
+    }
+
+    pub async fn subscribe(&self, subscription: Subscription) -> PostsClientResult<ActionHash> {
+        self.caller.call("subscribe", subscription).await
+    }
+
+    pub async fn unsubscribe(&self) -> PostsClientResult<()> {
+        self.caller.call("unsubscribe", ()).await
+    }
+
+    // Export and import
+
+    pub async fn export_my_content(&self) -> PostsClientResult<Vec<ExportedRecord>> {
+        self.caller.call("export_my_content", ()).await
+    }
+
+    pub async fn import_content(&self, input: ImportContentInput) -> PostsClientResult<ImportResult> {
+        self.caller.call("import_content", input).await
+    }
+
+    // Migration from a previous DNA
+
+    pub async fn get_migration_pointer(&self) -> PostsClientResult<Option<MigrationPointer>> {
+        self.caller.call("get_migration_pointer", ()).await
+    }
+
+    pub async fn record_migration_pointer(&self) -> PostsClientResult<Option<MigrationPointer>> {
+        self.caller.call("record_migration_pointer", ()).await
+    }
+
+    pub async fn get_previous_posts(&self) -> PostsClientResult<Vec<Link>> {
+        self.caller.call("get_previous_posts", ()).await
+    }
+
+    pub async fn get_migrated_post(&self, previous_post_hash: ActionHash) -> PostsClientResult<Option<Record>> {
+        self.caller.call("get_migrated_post", previous_post_hash).await
+    }
+
+    pub async fn migrate_my_posts(&self) -> PostsClientResult<Vec<Record>> {
+        self.caller.call("migrate_my_posts", ()).await
+    }
+
+    // Channels
+
+    pub async fn create_channel(&self, channel: Channel) -> PostsClientResult<Record> {
+        self.caller.call("create_channel", channel).await
+    }
+
+    pub async fn get_channel(&self, channel_hash: ActionHash) -> PostsClientResult<Option<Record>> {
+        self.caller.call("get_channel", channel_hash).await
+    }
+
+    pub async fn list_channels(&self) -> PostsClientResult<Vec<Link>> {
+        self.caller.call("list_channels", ()).await
+    }
+
+    pub async fn add_post_to_channel(&self, input: AddPostToChannelInput) -> PostsClientResult<ActionHash> {
+        self.caller.call("add_post_to_channel", input).await
+    }
+
+    pub async fn remove_post_from_channel(&self, input: ChannelPostInput) -> PostsClientResult<()> {
+        self.caller.call("remove_post_from_channel", input).await
+    }
+
+    pub async fn get_posts_for_channel(&self, channel_hash: ActionHash) -> PostsClientResult<Vec<Link>> {
+        self.caller.call("get_posts_for_channel", channel_hash).await
+    }
+}
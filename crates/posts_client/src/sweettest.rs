@@ -0,0 +1,44 @@
+use std::fmt::Debug;
+use std::future::Future;
+
+use hdk::prelude::*;
+use holochain::sweettest::{SweetCell, SweetConductor, SweetZome};
+
+use crate::{PostsClient, PostsClientError, PostsClientResult, ZomeCaller, POSTS_ZOME_NAME};
+
+// Calls the posts zome of a cell installed in a sweettest conductor
+pub struct SweetZomeCaller<'a> {
+    conductor: &'a SweetConductor,
+    zome: SweetZome,
+}
+
+impl<'a> SweetZomeCaller<'a> {
+    pub fn new(conductor: &'a SweetConductor, cell: &SweetCell) -> Self {
+        Self {
+            conductor,
+            zome: cell.zome(POSTS_ZOME_NAME),
+        }
+    }
+}
+
+impl ZomeCaller for SweetZomeCaller<'_> {
+    fn call<I, O>(&self, fn_name: &str, payload: I) -> impl Future<Output = PostsClientResult<O>> + Send
+    where
+        I: Serialize + Debug + Send,
+        O: DeserializeOwned + Debug + Send,
+    {
+        let fn_name = fn_name.to_string();
+        async move {
+            self.conductor
+                .call_fallible(&self.zome, fn_name, payload)
+                .await
+                .map_err(|e| PostsClientError::Call(format!("{:?}", e)))
+        }
+    }
+}
+
+impl<'a> PostsClient<SweetZomeCaller<'a>> {
+    pub fn sweettest(conductor: &'a SweetConductor, cell: &SweetCell) -> Self {
+        Self::new(SweetZomeCaller::new(conductor, cell))
+    }
+}
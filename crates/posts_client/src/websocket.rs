@@ -0,0 +1,55 @@
+use std::fmt::Debug;
+use std::future::Future;
+
+use hdk::prelude::*;
+use holochain_client::{AppWebsocket, ZomeCallTarget};
+
+use crate::{PostsClient, PostsClientError, PostsClientResult, ZomeCaller, POSTS_ZOME_NAME};
+
+// Calls the posts zome through an app websocket connected to a running conductor, in the
+// cell given by role name or cell id
+pub struct AppWebsocketCaller {
+    app_ws: AppWebsocket,
+    target: ZomeCallTarget,
+}
+
+impl AppWebsocketCaller {
+    pub fn new(app_ws: AppWebsocket, target: impl Into<ZomeCallTarget>) -> Self {
+        Self {
+            app_ws,
+            target: target.into(),
+        }
+    }
+
+    pub fn app_websocket(&self) -> &AppWebsocket {
+        &self.app_ws
+    }
+}
+
+impl ZomeCaller for AppWebsocketCaller {
+    fn call<I, O>(&self, fn_name: &str, payload: I) -> impl Future<Output = PostsClientResult<O>> + Send
+    where
+        I: Serialize + Debug + Send,
+        O: DeserializeOwned + Debug + Send,
+    {
+        let fn_name = FunctionName::from(fn_name);
+        async move {
+            let payload =
+                ExternIO::encode(payload).map_err(|e| PostsClientError::Serialization(e.to_string()))?;
+            let response = self
+                .app_ws
+                .call_zome(self.target.clone(), ZomeName::from(POSTS_ZOME_NAME), fn_name, payload)
+                .await
+                .map_err(|e| PostsClientError::Call(format!("{:?}", e)))?;
+            response
+                .decode()
+                .map_err(|e| PostsClientError::Serialization(e.to_string()))
+        }
+    }
+}
+
+impl PostsClient<AppWebsocketCaller> {
+    pub fn websocket(app_ws: AppWebsocket, target: impl Into<ZomeCallTarget>) -> Self {
+        Self::new(AppWebsocketCaller::new(app_ws, target))
+    }
+}
@@ -35,6 +35,8 @@
       imports = [
         ./zomes/integrity/posts/zome.nix
         ./zomes/coordinator/posts/zome.nix
+        ./zomes/integrity/file_storage/zome.nix
+        ./zomes/coordinator/file_storage/zome.nix
         # Just for testing purposes
         ./workdir/dna.nix
         ./workdir/happ.nix
@@ -11,6 +11,9 @@ import { mdiAlertCircleOutline, mdiDelete } from "@mdi/js";
 import '@shoelace-style/shoelace/dist/components/icon-button/icon-button.js';
 import '@shoelace-style/shoelace/dist/components/card/card.js';
 import '@shoelace-style/shoelace/dist/components/input/input.js';
+import '@shoelace-style/shoelace/dist/components/textarea/textarea.js';
+import '@shoelace-style/shoelace/dist/components/select/select.js';
+import '@shoelace-style/shoelace/dist/components/option/option.js';
 
 import SlAlert from '@shoelace-style/shoelace/dist/components/alert/alert.js';
 import '@shoelace-style/shoelace/dist/components/button/button.js';
@@ -58,6 +61,9 @@ export class CreatePost extends LitElement {
     const post: Post = {
       title: fields.title!,
       needs: (Array.isArray(fields.needs!) ? fields.needs! : [fields.needs!]).map((el: any) => el),
+      body: fields.body || '',
+      format: { type: (fields.format as any) || 'Plain' },
+      attachments: [],
     };
 
     try {
@@ -102,6 +108,15 @@ export class CreatePost extends LitElement {
             <sl-button @click=${() => { this._needsFields = [...this._needsFields, Math.max(...this._needsFields) + 1]; } }>${msg("Add Needs")}</sl-button>
           </div>          </div>
 
+          <div>
+          <sl-textarea name="body" .label=${msg("Body")}></sl-textarea>          </div>
+
+          <div>
+          <sl-select name="format" .label=${msg("Format")} value="Plain">
+            <sl-option value="Plain">${msg("Plain text")}</sl-option>
+            <sl-option value="Markdown">${msg("Markdown")}</sl-option>
+          </sl-select>          </div>
+
 
           <sl-button
             variant="primary"
@@ -10,6 +10,9 @@ import { mdiAlertCircleOutline, mdiDelete } from '@mdi/js';
 
 import '@shoelace-style/shoelace/dist/components/button/button.js';
 import '@shoelace-style/shoelace/dist/components/input/input.js';
+import '@shoelace-style/shoelace/dist/components/textarea/textarea.js';
+import '@shoelace-style/shoelace/dist/components/select/select.js';
+import '@shoelace-style/shoelace/dist/components/option/option.js';
 
 import '@shoelace-style/shoelace/dist/components/icon/icon.js';
 import SlAlert from '@shoelace-style/shoelace/dist/components/alert/alert.js';
@@ -61,6 +64,9 @@ export class EditPost extends LitElement {
     const post: Post = { 
       title: fields.title!,
       needs: (Array.isArray(fields.needs!) ? fields.needs! : [fields.needs!]).map((el: any) => el),
+      body: fields.body || '',
+      format: { type: (fields.format as any) || 'Plain' },
+      attachments: this.currentRecord.entry.attachments,
     };
 
     try {
@@ -109,6 +115,15 @@ export class EditPost extends LitElement {
           <sl-button @click=${() => { this._needsFields = [...this._needsFields, Math.max(...this._needsFields) + 1]; } }>${msg("Add Needs")}</sl-button>
         </div>          </div>
 
+          <div>
+        <sl-textarea name="body" .label=${msg("Body")} .defaultValue=${ this.currentRecord.entry.body }></sl-textarea>          </div>
+
+          <div>
+        <sl-select name="format" .label=${msg("Format")} .value=${ this.currentRecord.entry.format.type }>
+          <sl-option value="Plain">${msg("Plain text")}</sl-option>
+          <sl-option value="Markdown">${msg("Markdown")}</sl-option>
+        </sl-select>          </div>
+
 
 
           <div class="row" style="gap: 8px;">
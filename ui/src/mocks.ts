@@ -388,6 +388,9 @@ export async function samplePost(client: PostsClient, partialPost: Partial<Post>
         ...{
           title: "Lorem ipsum 2",
           needs: ["Lorem ipsum 2"],
+          body: "Lorem ipsum 2",
+          format: { type: 'Plain' },
+          attachments: [],
         },
         ...partialPost
     };
@@ -16,6 +16,7 @@ import { ActionCommittedSignal } from '@holochain-open-dev/utils';
 export type PostsSignal = ActionCommittedSignal<EntryTypes, LinkTypes>;
 
 export type EntryTypes =
+ | ({ type: 'Channel'; } & Channel)
  | ({ type: 'Certificate'; } & Certificate)
  | ({ type: 'Like'; } & Like)
  | ({ type: 'Comment'; } & Comment)
@@ -25,10 +26,23 @@ export type LinkTypes = string;
 
 
 
+export interface ContentFormat {
+  type:  
+    | 'Plain'
+        | 'Markdown'
+    ;
+}
+
 export interface Post { 
   title: string;
 
   needs: Array<string>;
+
+  body: string;
+
+  format: ContentFormat;
+
+  attachments: Array<ActionHash>;
 }
 
 
@@ -68,3 +82,18 @@ export interface Certificate {
   dna_hash: DnaHash;
 }
 
+
+
+
+export type PostingPolicy =
+  | { type: 'Anyone' }
+  | { type: 'Moderators' }
+  | { type: 'Members'; members: Array<AgentPubKey> };
+
+export interface Channel { 
+  name: string;
+
+  description: string;
+
+  posting_policy: PostingPolicy;
+}
@@ -17,6 +17,8 @@
           # This overrides all the "bundled" properties for the DNA manifest
           posts_integrity = self'.packages.posts_integrity;
           posts = self'.packages.posts;
+          file_storage_integrity = self'.packages.file_storage_integrity;
+          file_storage = self'.packages.file_storage;
         };
       };
   	};
@@ -0,0 +1,84 @@
+use hdk::prelude::*;
+use file_storage_integrity::*;
+// Files are uploaded chunk by chunk, each in its own call, and then tied together by a
+// FileManifest, whose ActionHash is what other zomes reference
+#[hdk_extern]
+pub fn upload_file_chunk(file_chunk: FileChunk) -> ExternResult<EntryHash> {
+    let file_chunk_hash = hash_entry(&file_chunk)?;
+    create_entry(&EntryTypes::FileChunk(file_chunk))?;
+    Ok(file_chunk_hash)
+}
+#[hdk_extern]
+pub fn create_file_manifest(file_manifest: FileManifest) -> ExternResult<ActionHash> {
+    create_entry(&EntryTypes::FileManifest(file_manifest))
+}
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct File {
+    pub manifest: FileManifest,
+    pub bytes: SerializedBytes,
+}
+fn get_entry_content<T>(hash: impl Into<AnyDhtHash>) -> ExternResult<Option<T>>
+where
+    T: TryFrom<SerializedBytes, Error = SerializedBytesError>,
+{
+    let Some(record) = get(hash, GetOptions::default())? else {
+        return Ok(None);
+    };
+    record.entry().to_app_option().map_err(|e| wasm_error!(e))
+}
+fn get_file_chunk_content(chunk_hash: &EntryHash) -> ExternResult<FileChunk> {
+    get_entry_content::<FileChunk>(chunk_hash.clone())?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest(format!("FileChunk {} was not found", chunk_hash))
+            ),
+        )
+}
+// Returns the manifest alone, so that clients can fetch its chunks one by one with
+// get_file_chunk instead of holding the whole file in a single response
+#[hdk_extern]
+pub fn get_file_manifest(file_manifest_hash: ActionHash) -> ExternResult<Option<FileManifest>> {
+    get_entry_content::<FileManifest>(file_manifest_hash)
+}
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct GetFileChunkInput {
+    pub file_manifest_hash: ActionHash,
+    pub index: usize,
+}
+#[hdk_extern]
+pub fn get_file_chunk(input: GetFileChunkInput) -> ExternResult<Option<FileChunk>> {
+    let Some(manifest) = get_entry_content::<FileManifest>(input.file_manifest_hash)? else {
+        return Ok(None);
+    };
+    let chunk_hash = manifest
+        .chunks
+        .get(input.index)
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest(
+                    format!(
+                        "FileManifest has {} chunks, but chunk {} was requested",
+                        manifest.chunks.len(), input.index
+                    ),
+                )
+            ),
+        )?;
+    get_file_chunk_content(chunk_hash).map(Some)
+}
+#[hdk_extern]
+pub fn get_file(file_manifest_hash: ActionHash) -> ExternResult<Option<File>> {
+    let Some(manifest) = get_entry_content::<FileManifest>(file_manifest_hash)? else {
+        return Ok(None);
+    };
+    let mut bytes = Vec::with_capacity(manifest.size);
+    for chunk_hash in manifest.chunks.iter() {
+        let file_chunk = get_file_chunk_content(chunk_hash)?;
+        bytes.extend_from_slice(file_chunk.0.bytes());
+    }
+    Ok(
+        Some(File {
+            manifest,
+            bytes: SerializedBytes::from(UnsafeBytes::from(bytes)),
+        }),
+    )
+}
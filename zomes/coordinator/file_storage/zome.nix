@@ -0,0 +1,14 @@
+{ inputs, rootPath, ... }:
+
+{
+  perSystem =
+    { inputs'
+    , ...
+    }: {
+      packages.file_storage = inputs.hc-infra.outputs.lib.rustZome {
+        workspacePath = rootPath;
+        holochain = inputs'.holochain;
+        crateCargoToml = ./Cargo.toml;
+      };
+    };
+}
@@ -1,10 +1,18 @@
 use hdk::prelude::*;
 use posts_integrity::*;
+use crate::block_list::filter_blocked_and_muted_authors;
+use crate::moderation_action::{filter_links, ListOptions};
 #[hdk_extern]
 pub fn get_all_posts() -> ExternResult<Vec<Link>> {
     let path = Path::from("all_posts");
-    get_links(
+    let links = get_links(
         GetLinksInputBuilder::try_new(path.path_entry_hash()?, LinkTypes::AllPosts)?
             .build(),
-    )
+    )?;
+    filter_blocked_and_muted_authors(links)
+}
+#[hdk_extern]
+pub fn get_all_posts_with_options(options: ListOptions) -> ExternResult<Vec<Link>> {
+    let links = get_all_posts()?;
+    filter_links(links, &options)
 }
@@ -0,0 +1,72 @@
+use hdk::prelude::*;
+use posts_integrity::*;
+use crate::private_entries::live_records;
+pub fn current_block_list() -> ExternResult<BlockList> {
+    let Some(record) = live_records(UnitEntryTypes::BlockList)?.pop() else {
+        return Ok(BlockList::default());
+    };
+    Ok(record.entry().to_app_option().map_err(|e| wasm_error!(e))?.unwrap_or_default())
+}
+fn save_block_list(block_list: BlockList) -> ExternResult<BlockList> {
+    match live_records(UnitEntryTypes::BlockList)?.pop() {
+        Some(record) => update_entry(record.action_address().clone(), &block_list)?,
+        None => create_entry(&EntryTypes::BlockList(block_list.clone()))?,
+    };
+    Ok(block_list)
+}
+#[hdk_extern]
+pub fn get_block_list() -> ExternResult<BlockList> {
+    current_block_list()
+}
+#[hdk_extern]
+pub fn block_agent(agent: AgentPubKey) -> ExternResult<BlockList> {
+    let mut block_list = current_block_list()?;
+    if !block_list.blocked_agents.contains(&agent) {
+        block_list.blocked_agents.push(agent);
+    }
+    save_block_list(block_list)
+}
+#[hdk_extern]
+pub fn unblock_agent(agent: AgentPubKey) -> ExternResult<BlockList> {
+    let mut block_list = current_block_list()?;
+    block_list.blocked_agents.retain(|blocked| blocked.ne(&agent));
+    save_block_list(block_list)
+}
+#[hdk_extern]
+pub fn mute_agent(agent: AgentPubKey) -> ExternResult<BlockList> {
+    let mut block_list = current_block_list()?;
+    if !block_list.muted_agents.contains(&agent) {
+        block_list.muted_agents.push(agent);
+    }
+    save_block_list(block_list)
+}
+#[hdk_extern]
+pub fn unmute_agent(agent: AgentPubKey) -> ExternResult<BlockList> {
+    let mut block_list = current_block_list()?;
+    block_list.muted_agents.retain(|muted| muted.ne(&agent));
+    save_block_list(block_list)
+}
+// Blocked agents are hidden everywhere: in feeds, in threads and from notifications
+pub fn filter_blocked_authors(links: Vec<Link>) -> ExternResult<Vec<Link>> {
+    let block_list = current_block_list()?;
+    Ok(
+        links
+            .into_iter()
+            .filter(|link| !block_list.blocked_agents.contains(&link.author))
+            .collect(),
+    )
+}
+// Muted agents are only hidden from feeds, so their replies and likes on content the agent is
+// already looking at, and the notifications they cause, are still shown
+pub fn filter_blocked_and_muted_authors(links: Vec<Link>) -> ExternResult<Vec<Link>> {
+    let block_list = current_block_list()?;
+    Ok(
+        links
+            .into_iter()
+            .filter(|link| {
+                !block_list.blocked_agents.contains(&link.author)
+                    && !block_list.muted_agents.contains(&link.author)
+            })
+            .collect(),
+    )
+}
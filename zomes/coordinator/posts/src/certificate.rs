@@ -1,5 +1,6 @@
 use hdk::prelude::*;
 use posts_integrity::*;
+use crate::notification::notify;
 #[hdk_extern]
 pub fn create_certificate(certificate: Certificate) -> ExternResult<Record> {
     let certificate_hash = create_entry(&EntryTypes::Certificate(certificate.clone()))?;
@@ -30,6 +31,14 @@ pub fn create_certificate(certificate: Certificate) -> ExternResult<Record> {
                 .to_string())
             ),
         )?;
+    notify(
+        NotificationEvent::CertificateCreated {
+            post_hash: certificate.post_hash,
+            certificate_hash,
+        },
+        record.clone(),
+        vec![certificate.agent],
+    )?;
     Ok(record)
 }
 #[hdk_extern]
@@ -0,0 +1,80 @@
+use hdk::prelude::*;
+use posts_integrity::*;
+use crate::block_list::filter_blocked_and_muted_authors;
+#[hdk_extern]
+pub fn create_channel(channel: Channel) -> ExternResult<Record> {
+    let channel_hash = create_entry(&EntryTypes::Channel(channel))?;
+    let path = Path::from("all_channels");
+    create_link(
+        path.path_entry_hash()?,
+        channel_hash.clone(),
+        LinkTypes::AllChannels,
+        (),
+    )?;
+    let record = get(channel_hash.clone(), GetOptions::default())?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("Could not find the newly created Channel"
+                .to_string())
+            ),
+        )?;
+    Ok(record)
+}
+#[hdk_extern]
+pub fn get_channel(channel_hash: ActionHash) -> ExternResult<Option<Record>> {
+    get(channel_hash, GetOptions::default())
+}
+#[hdk_extern]
+pub fn list_channels() -> ExternResult<Vec<Link>> {
+    let path = Path::from("all_channels");
+    get_links(
+        GetLinksInputBuilder::try_new(path.path_entry_hash()?, LinkTypes::AllChannels)?
+            .build(),
+    )
+}
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ChannelPostInput {
+    pub channel_hash: ActionHash,
+    pub post_hash: ActionHash,
+}
+#[derive(Serialize, Deserialize, Debug)]
+pub struct AddPostToChannelInput {
+    pub channel_hash: ActionHash,
+    pub post_hash: ActionHash,
+    // Needed to post to a Moderators channel as a moderator by certificate
+    pub moderator_certificate_hash: Option<ActionHash>,
+}
+#[hdk_extern]
+pub fn add_post_to_channel(input: AddPostToChannelInput) -> ExternResult<ActionHash> {
+    let tag = match input.moderator_certificate_hash {
+        Some(moderator_certificate_hash) => {
+            LinkTag::new(
+                ExternIO::encode(moderator_certificate_hash).map_err(|e| wasm_error!(e))?.0,
+            )
+        }
+        None => LinkTag::new(Vec::<u8>::new()),
+    };
+    create_link(input.channel_hash, input.post_hash, LinkTypes::ChannelToPosts, tag)
+}
+#[hdk_extern]
+pub fn remove_post_from_channel(input: ChannelPostInput) -> ExternResult<()> {
+    let links = get_links(
+        GetLinksInputBuilder::try_new(input.channel_hash, LinkTypes::ChannelToPosts)?
+            .build(),
+    )?;
+    for link in links {
+        if let Some(post_hash) = link.target.clone().into_action_hash() {
+            if post_hash == input.post_hash {
+                delete_link(link.create_link_hash)?;
+            }
+        }
+    }
+    Ok(())
+}
+#[hdk_extern]
+pub fn get_posts_for_channel(channel_hash: ActionHash) -> ExternResult<Vec<Link>> {
+    let links = get_links(
+        GetLinksInputBuilder::try_new(channel_hash, LinkTypes::ChannelToPosts)?.build(),
+    )?;
+    filter_blocked_and_muted_authors(links)
+}
@@ -1,5 +1,8 @@
 use hdk::prelude::*;
 use posts_integrity::*;
+use crate::block_list::filter_blocked_authors;
+use crate::moderation_action::{filter_links, ListOptions};
+use crate::notification::notify;
 #[hdk_extern]
 pub fn create_comment(comment: Comment) -> ExternResult<Record> {
     let comment_hash = create_entry(&EntryTypes::Comment(comment.clone()))?;
@@ -16,6 +19,16 @@ pub fn create_comment(comment: Comment) -> ExternResult<Record> {
                 .to_string())
             ),
         )?;
+    if let Some(post_record) = get(comment.post_hash.clone(), GetOptions::default())? {
+        notify(
+            NotificationEvent::CommentCreated {
+                post_hash: comment.post_hash,
+                comment_hash,
+            },
+            record.clone(),
+            vec![post_record.action().author().clone()],
+        )?;
+    }
     Ok(record)
 }
 #[hdk_extern]
@@ -163,9 +176,22 @@ pub fn get_oldest_delete_for_comment(
 }
 #[hdk_extern]
 pub fn get_comments_for_post(post_hash: ActionHash) -> ExternResult<Vec<Link>> {
-    get_links(
+    let links = get_links(
         GetLinksInputBuilder::try_new(post_hash, LinkTypes::PostToComments)?.build(),
-    )
+    )?;
+    filter_blocked_authors(links)
+}
+#[derive(Serialize, Deserialize, Debug)]
+pub struct GetCommentsForPostInput {
+    pub post_hash: ActionHash,
+    pub options: ListOptions,
+}
+#[hdk_extern]
+pub fn get_comments_for_post_with_options(
+    input: GetCommentsForPostInput,
+) -> ExternResult<Vec<Link>> {
+    let links = get_comments_for_post(input.post_hash)?;
+    filter_links(links, &input.options)
 }
 #[hdk_extern]
 pub fn get_deleted_comments_for_post(
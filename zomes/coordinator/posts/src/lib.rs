@@ -1,3 +1,15 @@
+pub mod channel;
+pub mod migration;
+pub mod portable_content;
+pub mod block_list;
+pub mod report;
+pub mod moderation_action;
+pub mod scheduled_post;
+pub mod post_draft;
+pub mod private_entries;
+pub mod subscription;
+pub mod pagination;
+pub mod notification;
 pub mod posts_by_author_entry_hash;
 pub mod all_posts_entry_hash;
 pub mod posts_by_author;
@@ -6,10 +18,19 @@ pub mod certificate;
 pub mod like;
 pub mod comment;
 pub mod post;
+use std::collections::BTreeSet;
 use hdk::prelude::*;
 use posts_integrity::*;
 #[hdk_extern]
 pub fn init(_: ()) -> ExternResult<InitCallbackResult> {
+    let mut functions = BTreeSet::new();
+    functions.insert((zome_info()?.name, FunctionName("recv_remote_signal".into())));
+    create_cap_grant(CapGrantEntry {
+        tag: "".into(),
+        access: CapAccess::Unrestricted,
+        functions: GrantedFunctions::Listed(functions),
+    })?;
+    schedule("publish_scheduled_posts")?;
     Ok(InitCallbackResult::Pass)
 }
 #[derive(Serialize, Deserialize, Debug)]
@@ -28,26 +49,55 @@ pub enum Signal {
         original_app_entry: EntryTypes,
     },
     EntryDeleted { action: SignedActionHashed, original_app_entry: EntryTypes },
+    Notification {
+        from: AgentPubKey,
+        event: NotificationEvent,
+        action: SignedActionHashed,
+    },
 }
 #[hdk_extern(infallible)]
 pub fn post_commit(committed_actions: Vec<SignedActionHashed>) {
+    let subscription = match subscription::current_subscription() {
+        Ok(subscription) => subscription,
+        Err(err) => {
+            error!("Error reading signal subscription: {:?}", err);
+            None
+        }
+    };
     for action in committed_actions {
-        if let Err(err) = signal_action(action) {
+        if let Err(err) = signal_action(action, subscription.as_ref()) {
             error!("Error signaling new action: {:?}", err);
         }
     }
 }
-fn signal_action(action: SignedActionHashed) -> ExternResult<()> {
+fn emit_subscribed_signal(
+    signal: Signal,
+    subscription: Option<&Subscription>,
+) -> ExternResult<()> {
+    if let Some(subscription) = subscription {
+        if !subscription::signal_matches(&signal, subscription) {
+            return Ok(());
+        }
+    }
+    emit_signal(signal)
+}
+fn signal_action(
+    action: SignedActionHashed,
+    subscription: Option<&Subscription>,
+) -> ExternResult<()> {
     match action.hashed.content.clone() {
         Action::CreateLink(create_link) => {
             if let Ok(Some(link_type)) = LinkTypes::from_type(
                 create_link.zome_index,
                 create_link.link_type,
             ) {
-                emit_signal(Signal::LinkCreated {
-                    action,
-                    link_type,
-                })?;
+                emit_subscribed_signal(
+                    Signal::LinkCreated {
+                        action,
+                        link_type,
+                    },
+                    subscription,
+                )?;
             }
             Ok(())
         }
@@ -68,11 +118,14 @@ fn signal_action(action: SignedActionHashed) -> ExternResult<()> {
                         create_link.zome_index,
                         create_link.link_type,
                     ) {
-                        emit_signal(Signal::LinkDeleted {
-                            action,
-                            link_type,
-                            create_link_action: record.signed_action.clone(),
-                        })?;
+                        emit_subscribed_signal(
+                            Signal::LinkDeleted {
+                                action,
+                                link_type,
+                                create_link_action: record.signed_action.clone(),
+                            },
+                            subscription,
+                        )?;
                     }
                     Ok(())
                 }
@@ -87,10 +140,13 @@ fn signal_action(action: SignedActionHashed) -> ExternResult<()> {
         }
         Action::Create(_create) => {
             if let Ok(Some(app_entry)) = get_entry_for_action(&action.hashed.hash) {
-                emit_signal(Signal::EntryCreated {
-                    action,
-                    app_entry,
-                })?;
+                emit_subscribed_signal(
+                    Signal::EntryCreated {
+                        action,
+                        app_entry,
+                    },
+                    subscription,
+                )?;
             }
             Ok(())
         }
@@ -99,11 +155,14 @@ fn signal_action(action: SignedActionHashed) -> ExternResult<()> {
                 if let Ok(Some(original_app_entry)) = get_entry_for_action(
                     &update.original_action_address,
                 ) {
-                    emit_signal(Signal::EntryUpdated {
-                        action,
-                        app_entry,
-                        original_app_entry,
-                    })?;
+                    emit_subscribed_signal(
+                        Signal::EntryUpdated {
+                            action,
+                            app_entry,
+                            original_app_entry,
+                        },
+                        subscription,
+                    )?;
                 }
             }
             Ok(())
@@ -112,10 +171,13 @@ fn signal_action(action: SignedActionHashed) -> ExternResult<()> {
             if let Ok(Some(original_app_entry)) = get_entry_for_action(
                 &delete.deletes_address,
             ) {
-                emit_signal(Signal::EntryDeleted {
-                    action,
-                    original_app_entry,
-                })?;
+                emit_subscribed_signal(
+                    Signal::EntryDeleted {
+                        action,
+                        original_app_entry,
+                    },
+                    subscription,
+                )?;
             }
             Ok(())
         }
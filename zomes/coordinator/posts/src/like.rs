@@ -1,8 +1,11 @@
 use hdk::prelude::*;
 use posts_integrity::*;
+use crate::block_list::filter_blocked_authors;
+use crate::notification::notify;
 #[hdk_extern]
 pub fn create_like(like: Like) -> ExternResult<Record> {
     let like_hash = create_entry(&EntryTypes::Like(like.clone()))?;
+    create_link(like.agent.clone(), like_hash.clone(), LinkTypes::AgentToLikes, ())?;
     if let Some(base) = like.like_hash.clone() {
         create_link(base, like_hash.clone(), LinkTypes::LikeToLikes, ())?;
     }
@@ -13,6 +16,17 @@ pub fn create_like(like: Like) -> ExternResult<Record> {
                 .to_string())
             ),
         )?;
+    let mut recipients = vec![like.agent.clone()];
+    if let Some(liked_hash) = like.like_hash {
+        if let Some(liked_record) = get(liked_hash, GetOptions::default())? {
+            recipients.push(liked_record.action().author().clone());
+        }
+    }
+    notify(
+        NotificationEvent::LikeCreated { like_hash },
+        record.clone(),
+        recipients,
+    )?;
     Ok(record)
 }
 #[hdk_extern]
@@ -99,7 +113,10 @@ pub fn get_oldest_delete_for_like(
 }
 #[hdk_extern]
 pub fn get_likes_for_like(like_hash: ActionHash) -> ExternResult<Vec<Link>> {
-    get_links(GetLinksInputBuilder::try_new(like_hash, LinkTypes::LikeToLikes)?.build())
+    let links = get_links(
+        GetLinksInputBuilder::try_new(like_hash, LinkTypes::LikeToLikes)?.build(),
+    )?;
+    filter_blocked_authors(links)
 }
 #[hdk_extern]
 pub fn get_deleted_likes_for_like(
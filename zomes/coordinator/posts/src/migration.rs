@@ -0,0 +1,166 @@
+use hdk::prelude::*;
+use posts_integrity::*;
+fn oldest_link_target(links: Vec<Link>) -> ExternResult<Option<ActionHash>> {
+    let Some(link) = links.into_iter().min_by_key(|link| link.timestamp) else {
+        return Ok(None);
+    };
+    let action_hash = link
+        .target
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("No action hash associated with link".to_string())
+            ),
+        )?;
+    Ok(Some(action_hash))
+}
+pub fn current_migration_pointer() -> ExternResult<Option<MigrationPointer>> {
+    let path = Path::from("migration_pointers");
+    let links = get_links(
+        GetLinksInputBuilder::try_new(path.path_entry_hash()?, LinkTypes::MigrationPointers)?
+            .build(),
+    )?;
+    let Some(migration_pointer_hash) = oldest_link_target(links)? else {
+        return Ok(None);
+    };
+    let Some(record) = get(migration_pointer_hash, GetOptions::default())? else {
+        return Ok(None);
+    };
+    record.entry().to_app_option().map_err(|e| wasm_error!(e))
+}
+#[hdk_extern]
+pub fn get_migration_pointer() -> ExternResult<Option<MigrationPointer>> {
+    current_migration_pointer()
+}
+// Records the previous DNA declared in the DNA properties, unless it has been recorded already
+#[hdk_extern]
+pub fn record_migration_pointer() -> ExternResult<Option<MigrationPointer>> {
+    if let Some(migration_pointer) = current_migration_pointer()? {
+        return Ok(Some(migration_pointer));
+    }
+    let Some(previous_dna_hash) = posts_properties()?.previous_dna_hash else {
+        return Ok(None);
+    };
+    let migration_pointer = MigrationPointer {
+        previous_dna_hash,
+    };
+    let migration_pointer_hash = create_entry(
+        &EntryTypes::MigrationPointer(migration_pointer.clone()),
+    )?;
+    let path = Path::from("migration_pointers");
+    create_link(
+        path.path_entry_hash()?,
+        migration_pointer_hash,
+        LinkTypes::MigrationPointers,
+        (),
+    )?;
+    Ok(Some(migration_pointer))
+}
+// Calls this zome in the agent's own cell of the previous DNA, which must be installed
+// in the same conductor
+fn call_previous_dna<I, O>(fn_name: &str, payload: I) -> ExternResult<O>
+where
+    I: Serialize + std::fmt::Debug,
+    O: serde::de::DeserializeOwned + std::fmt::Debug,
+{
+    let migration_pointer = current_migration_pointer()?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("This DNA has no MigrationPointer".to_string())
+            ),
+        )?;
+    let cell_id = CellId::new(
+        migration_pointer.previous_dna_hash,
+        agent_info()?.agent_initial_pubkey,
+    );
+    match call(
+        CallTargetCell::OtherCell(cell_id),
+        zome_info()?.name,
+        fn_name.into(),
+        None,
+        payload,
+    )? {
+        ZomeCallResponse::Ok(response) => response.decode().map_err(|e| wasm_error!(e)),
+        response => {
+            Err(
+                wasm_error!(
+                    WasmErrorInner::Guest(format!("Call to the previous DNA failed: {:?}",
+                    response))
+                ),
+            )
+        }
+    }
+}
+#[hdk_extern]
+pub fn get_previous_posts() -> ExternResult<Vec<Link>> {
+    call_previous_dna("get_all_posts", ())
+}
+// Returns the copy of a Post of the previous DNA, if its author has migrated it. Should the
+// author have migrated it more than once, the first copy is the one that counts
+#[hdk_extern]
+pub fn get_migrated_post(previous_post_hash: ActionHash) -> ExternResult<Option<Record>> {
+    let links = get_links(
+        GetLinksInputBuilder::try_new(previous_post_hash, LinkTypes::MigratedPosts)?.build(),
+    )?;
+    let Some(migrated_post_hash) = oldest_link_target(links)? else {
+        return Ok(None);
+    };
+    crate::post::get_latest_post(migrated_post_hash)
+}
+// Copies the latest revision of each of the agent's own Posts of the previous DNA that has not
+// been copied yet, and returns the copies. The link to each copy carries the signed action of
+// the copied revision, so that validators can check that the copy is faithful.
+#[hdk_extern]
+pub fn migrate_my_posts() -> ExternResult<Vec<Record>> {
+    let my_pub_key = agent_info()?.agent_initial_pubkey;
+    let mut migrated = vec![];
+    for link in call_previous_dna::<_, Vec<Link>>("get_all_posts", ())? {
+        if link.author.ne(&my_pub_key) {
+            continue;
+        }
+        let Some(previous_post_hash) = link.target.into_action_hash() else {
+            continue;
+        };
+        let existing_links = get_links(
+            GetLinksInputBuilder::try_new(
+                    previous_post_hash.clone(),
+                    LinkTypes::MigratedPosts,
+                )?
+                .build(),
+        )?;
+        if !existing_links.is_empty() {
+            continue;
+        }
+        let Some(previous_record) = call_previous_dna::<
+            _,
+            Option<Record>,
+        >("get_latest_post", previous_post_hash.clone())? else {
+            continue;
+        };
+        if previous_record.action().author().ne(&my_pub_key) {
+            continue;
+        }
+        let post: Post = previous_record
+            .entry()
+            .to_app_option()
+            .map_err(|e| wasm_error!(e))?
+            .ok_or(
+                wasm_error!(
+                    WasmErrorInner::Guest("Previous Post record has no entry".to_string())
+                ),
+            )?;
+        let record = crate::post::create_post(post)?;
+        create_link(
+            previous_post_hash,
+            record.action_address().clone(),
+            LinkTypes::MigratedPosts,
+            LinkTag::new(
+                ExternIO::encode(previous_record.signed_action)
+                    .map_err(|e| wasm_error!(e))?
+                    .0,
+            ),
+        )?;
+        migrated.push(record);
+    }
+    Ok(migrated)
+}
@@ -0,0 +1,77 @@
+use hdk::prelude::*;
+use posts_integrity::*;
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ListOptions {
+    pub exclude_moderated: bool,
+}
+#[hdk_extern]
+pub fn create_moderation_action(moderation_action: ModerationAction) -> ExternResult<Record> {
+    let moderation_action_hash = create_entry(
+        &EntryTypes::ModerationAction(moderation_action.clone()),
+    )?;
+    create_link(
+        moderation_action.target_hash.clone(),
+        moderation_action_hash.clone(),
+        LinkTypes::ModeratedToModerationActions,
+        (),
+    )?;
+    let record = get(moderation_action_hash.clone(), GetOptions::default())?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("Could not find the newly created ModerationAction"
+                .to_string())
+            ),
+        )?;
+    Ok(record)
+}
+#[hdk_extern]
+pub fn get_moderation_actions_for(target_hash: ActionHash) -> ExternResult<Vec<Link>> {
+    get_links(
+        GetLinksInputBuilder::try_new(target_hash, LinkTypes::ModeratedToModerationActions)?
+            .build(),
+    )
+}
+pub fn is_hidden(target_hash: ActionHash) -> ExternResult<bool> {
+    let latest_link = get_moderation_actions_for(target_hash)?
+        .into_iter()
+        .max_by(|link_a, link_b| link_a.timestamp.cmp(&link_b.timestamp));
+    let Some(latest_link) = latest_link else {
+        return Ok(false);
+    };
+    let action_hash = latest_link
+        .target
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("No action hash associated with link".to_string())
+            ),
+        )?;
+    let Some(record) = get(action_hash, GetOptions::default())? else {
+        return Ok(false);
+    };
+    let moderation_action: Option<ModerationAction> = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?;
+    Ok(
+        matches!(
+            moderation_action,
+            Some(ModerationAction { decision: ModerationDecision::Hide, .. })
+        ),
+    )
+}
+pub fn filter_links(links: Vec<Link>, options: &ListOptions) -> ExternResult<Vec<Link>> {
+    if !options.exclude_moderated {
+        return Ok(links);
+    }
+    let mut visible_links = vec![];
+    for link in links {
+        if let Some(target_hash) = link.target.clone().into_action_hash() {
+            if is_hidden(target_hash)? {
+                continue;
+            }
+        }
+        visible_links.push(link);
+    }
+    Ok(visible_links)
+}
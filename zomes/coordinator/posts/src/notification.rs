@@ -0,0 +1,271 @@
+use std::collections::BTreeSet;
+use hdk::prelude::*;
+use posts_integrity::*;
+use crate::block_list::filter_blocked_authors;
+use crate::pagination::{paginate, Page};
+use crate::private_entries::live_records;
+use crate::Signal;
+// Carries the whole signed Record, since the receiver can't fetch it from the DHT before the
+// sender's call has finished and published it
+#[derive(Serialize, Deserialize, Debug)]
+pub struct RemoteNotification {
+    pub event: NotificationEvent,
+    pub record: Record,
+}
+pub fn notify(
+    event: NotificationEvent,
+    record: Record,
+    recipients: Vec<AgentPubKey>,
+) -> ExternResult<()> {
+    let my_pub_key = agent_info()?.agent_latest_pubkey;
+    let mut recipients: Vec<AgentPubKey> = recipients
+        .into_iter()
+        .filter(|agent| agent.ne(&my_pub_key))
+        .collect();
+    recipients.sort();
+    recipients.dedup();
+    if recipients.is_empty() {
+        return Ok(());
+    }
+    send_remote_signal(RemoteNotification { event, record }, recipients)
+}
+fn entry_of_type<T>(record: &Record, unit_entry_type: UnitEntryTypes) -> ExternResult<Option<T>>
+where
+    T: TryFrom<SerializedBytes, Error = SerializedBytesError>,
+{
+    let entry_type: EntryType = unit_entry_type.try_into()?;
+    if record.action().entry_type() != Some(&entry_type) {
+        return Ok(None);
+    }
+    record.entry().to_app_option().map_err(|e| wasm_error!(e))
+}
+fn author_of(action_hash: ActionHash) -> ExternResult<Option<AgentPubKey>> {
+    Ok(
+        get(action_hash, GetOptions::default())?
+            .map(|record| record.action().author().clone()),
+    )
+}
+// Checks that the sender signed the record of the event, and that the event concerns this agent
+fn is_genuine(from: &AgentPubKey, notification: &RemoteNotification) -> ExternResult<bool> {
+    let record = &notification.record;
+    let action = record.action();
+    if action.author().ne(from) {
+        return Ok(false);
+    }
+    let action_hash = hash_action(action.clone())?;
+    if action_hash.ne(record.action_address())
+        || action_hash.ne(notification.event.action_hash())
+    {
+        return Ok(false);
+    }
+    if !verify_signature(from.clone(), record.signature().clone(), action.clone())? {
+        return Ok(false);
+    }
+    let Some(entry) = record.entry().as_option() else {
+        return Ok(false);
+    };
+    if Some(&hash_entry(entry.clone())?) != action.entry_hash() {
+        return Ok(false);
+    }
+    let my_pub_key = agent_info()?.agent_latest_pubkey;
+    match &notification.event {
+        NotificationEvent::CommentCreated { post_hash, .. } => {
+            let Some(comment) = entry_of_type::<Comment>(record, UnitEntryTypes::Comment)? else {
+                return Ok(false);
+            };
+            Ok(
+                comment.post_hash.eq(post_hash)
+                    && author_of(post_hash.clone())? == Some(my_pub_key),
+            )
+        }
+        NotificationEvent::LikeCreated { .. } => {
+            let Some(like) = entry_of_type::<Like>(record, UnitEntryTypes::Like)? else {
+                return Ok(false);
+            };
+            if like.agent.eq(&my_pub_key) {
+                return Ok(true);
+            }
+            match like.like_hash {
+                Some(liked_hash) => Ok(author_of(liked_hash)? == Some(my_pub_key)),
+                None => Ok(false),
+            }
+        }
+        NotificationEvent::CertificateCreated { post_hash, .. } => {
+            let Some(certificate) = entry_of_type::<
+                Certificate,
+            >(record, UnitEntryTypes::Certificate)? else {
+                return Ok(false);
+            };
+            Ok(certificate.post_hash.eq(post_hash) && certificate.agent.eq(&my_pub_key))
+        }
+    }
+}
+// Notifications that can't be verified are dropped without an error, since the sender doesn't
+// wait for the outcome anyway
+#[hdk_extern]
+pub fn recv_remote_signal(notification: RemoteNotification) -> ExternResult<()> {
+    let from = call_info()?.provenance;
+    if !is_genuine(&from, &notification)? {
+        return Ok(());
+    }
+    // Muted agents still notify, like they still show up in the inbox
+    if crate::block_list::current_block_list()?.blocked_agents.contains(&from) {
+        return Ok(());
+    }
+    emit_signal(Signal::Notification {
+        from,
+        event: notification.event,
+        action: notification.record.signed_action,
+    })
+}
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Notification {
+    pub from: AgentPubKey,
+    pub event: NotificationEvent,
+}
+#[derive(Serialize, Deserialize, Debug)]
+pub struct NotificationWithStatus {
+    pub notification_hash: ActionHash,
+    pub notification: Notification,
+    pub timestamp: Timestamp,
+    pub read: bool,
+}
+fn my_created_actions(entry_type: UnitEntryTypes) -> ExternResult<Vec<ActionHash>> {
+    let records = query(
+        ChainQueryFilter::new()
+            .entry_type(entry_type.try_into()?)
+            .action_type(ActionType::Create),
+    )?;
+    Ok(records.into_iter().map(|record| record.action_address().clone()).collect())
+}
+fn get_links_to_me(
+    base: impl Into<AnyLinkableHash>,
+    link_type: LinkTypes,
+    my_pub_key: &AgentPubKey,
+) -> ExternResult<Vec<Link>> {
+    let links = get_links(GetLinksInputBuilder::try_new(base, link_type)?.build())?;
+    Ok(links.into_iter().filter(|link| link.author.ne(my_pub_key)).collect())
+}
+// The inbox is rebuilt from the links other agents left on this agent's content, so nothing is
+// lost while the agent is offline and no remote signal arrives
+fn inbox() -> ExternResult<Vec<NotificationWithStatus>> {
+    let my_pub_key = agent_info()?.agent_latest_pubkey;
+    let read_state = current_read_state()?;
+    let mut notifications: Vec<(Link, NotificationEvent)> = vec![];
+    for post_hash in my_created_actions(UnitEntryTypes::Post)? {
+        let links = get_links_to_me(post_hash.clone(), LinkTypes::PostToComments, &my_pub_key)?;
+        for link in filter_blocked_authors(links)? {
+            if let Some(comment_hash) = link.target.clone().into_action_hash() {
+                let event = NotificationEvent::CommentCreated {
+                    post_hash: post_hash.clone(),
+                    comment_hash,
+                };
+                notifications.push((link, event));
+            }
+        }
+    }
+    let mut like_links =
+        get_links_to_me(my_pub_key.clone(), LinkTypes::AgentToLikes, &my_pub_key)?;
+    for like_hash in my_created_actions(UnitEntryTypes::Like)? {
+        like_links.extend(get_links_to_me(like_hash, LinkTypes::LikeToLikes, &my_pub_key)?);
+    }
+    for link in filter_blocked_authors(like_links)? {
+        if let Some(like_hash) = link.target.clone().into_action_hash() {
+            notifications.push((link, NotificationEvent::LikeCreated { like_hash }));
+        }
+    }
+    let certificate_links = get_links_to_me(
+        my_pub_key.clone(),
+        LinkTypes::CertifiedToCertificates,
+        &my_pub_key,
+    )?;
+    for link in filter_blocked_authors(certificate_links)? {
+        let Some(certificate_hash) = link.target.clone().into_action_hash() else {
+            continue;
+        };
+        let Some(record) = get(certificate_hash.clone(), GetOptions::default())? else {
+            continue;
+        };
+        if let Some(certificate) = entry_of_type::<
+            Certificate,
+        >(&record, UnitEntryTypes::Certificate)? {
+            let event = NotificationEvent::CertificateCreated {
+                post_hash: certificate.post_hash,
+                certificate_hash,
+            };
+            notifications.push((link, event));
+        }
+    }
+    let mut notifications: Vec<NotificationWithStatus> = notifications
+        .into_iter()
+        .map(|(link, event)| {
+            let notification_hash = event.action_hash().clone();
+            NotificationWithStatus {
+                read: link.timestamp <= read_state.read_until
+                    || read_state.read_hashes.contains(&notification_hash),
+                notification_hash,
+                notification: Notification {
+                    from: link.author,
+                    event,
+                },
+                timestamp: link.timestamp,
+            }
+        })
+        .collect();
+    notifications.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    let mut seen = BTreeSet::new();
+    notifications.retain(|notification| seen.insert(notification.notification_hash.clone()));
+    Ok(notifications)
+}
+fn current_read_state() -> ExternResult<NotificationReadState> {
+    let Some(record) = live_records(UnitEntryTypes::NotificationReadState)?.pop() else {
+        return Ok(NotificationReadState::default());
+    };
+    Ok(record.entry().to_app_option().map_err(|e| wasm_error!(e))?.unwrap_or_default())
+}
+fn save_read_state(read_state: NotificationReadState) -> ExternResult<()> {
+    match live_records(UnitEntryTypes::NotificationReadState)?.pop() {
+        Some(record) => update_entry(record.action_address().clone(), &read_state)?,
+        None => create_entry(&EntryTypes::NotificationReadState(read_state))?,
+    };
+    Ok(())
+}
+#[hdk_extern]
+pub fn get_notifications(page: Page) -> ExternResult<Vec<NotificationWithStatus>> {
+    Ok(paginate(inbox()?, &page))
+}
+#[hdk_extern]
+pub fn mark_notifications_read(notification_hashes: Vec<ActionHash>) -> ExternResult<()> {
+    let mut read_state = current_read_state()?;
+    read_state.read_hashes.extend(notification_hashes);
+    // Moves the marker past every notification that is read from the oldest one on, so that
+    // the list of explicitly read hashes stays short
+    let notifications = inbox()?;
+    for notification in notifications.iter().rev() {
+        if notification.timestamp <= read_state.read_until {
+            continue;
+        }
+        if !read_state.read_hashes.contains(&notification.notification_hash) {
+            break;
+        }
+        read_state.read_until = notification.timestamp;
+    }
+    let read_until = read_state.read_until;
+    read_state
+        .read_hashes
+        .retain(|hash| {
+            notifications
+                .iter()
+                .any(|notification| {
+                    notification.notification_hash.eq(hash)
+                        && notification.timestamp > read_until
+                })
+        });
+    read_state.read_hashes.sort();
+    read_state.read_hashes.dedup();
+    save_read_state(read_state)
+}
+#[hdk_extern]
+pub fn get_unread_count() -> ExternResult<usize> {
+    Ok(inbox()?.into_iter().filter(|notification| !notification.read).count())
+}
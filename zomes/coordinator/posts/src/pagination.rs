@@ -0,0 +1,9 @@
+use hdk::prelude::*;
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Page {
+    pub page: usize,
+    pub page_size: usize,
+}
+pub fn paginate<T>(items: Vec<T>, page: &Page) -> Vec<T> {
+    items.into_iter().skip(page.page * page.page_size).take(page.page_size).collect()
+}
@@ -0,0 +1,309 @@
+use std::collections::{HashMap, HashSet};
+use hdk::prelude::*;
+use posts_integrity::*;
+use crate::comment::UpdateCommentInput;
+use crate::post::UpdatePostInput;
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub enum ExportedContent {
+    Post(Post),
+    Comment(Comment),
+    Like(Like),
+    Certificate(Certificate),
+}
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ExportedRecord {
+    pub action_hash: ActionHash,
+    pub entry_hash: EntryHash,
+    pub timestamp: Timestamp,
+    pub previous_action_hash: Option<ActionHash>,
+    pub content: ExportedContent,
+}
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ImportedRecord {
+    pub exported_action_hash: ActionHash,
+    pub action_hash: ActionHash,
+    pub exported_entry_hash: EntryHash,
+    pub entry_hash: EntryHash,
+    pub exported_previous_action_hash: Option<ActionHash>,
+}
+fn exported_content(record: &Record) -> ExternResult<Option<ExportedContent>> {
+    let Some(EntryType::App(app_entry_def)) = record.action().entry_type() else {
+        return Ok(None);
+    };
+    let Some(entry) = record.entry().as_option() else {
+        return Ok(None);
+    };
+    let content = match EntryTypes::deserialize_from_type(
+        app_entry_def.zome_index,
+        app_entry_def.entry_index,
+        entry,
+    )? {
+        Some(EntryTypes::Post(post)) => ExportedContent::Post(post),
+        Some(EntryTypes::Comment(comment)) => ExportedContent::Comment(comment),
+        Some(EntryTypes::Like(like)) => ExportedContent::Like(like),
+        Some(EntryTypes::Certificate(certificate)) => {
+            ExportedContent::Certificate(certificate)
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(content))
+}
+// Every Post revision, Comment revision, Like and Certificate on the agent's source chain that
+// is still live, in the order they were authored. Revisions of deleted records are left out too
+#[hdk_extern]
+pub fn export_my_content() -> ExternResult<Vec<ExportedRecord>> {
+    let records = query(ChainQueryFilter::new().include_entries(true))?;
+    let mut removed: HashSet<ActionHash> = records
+        .iter()
+        .filter_map(|record| match record.action() {
+            Action::Delete(delete) => Some(delete.deletes_address.clone()),
+            _ => None,
+        })
+        .collect();
+    let mut exported = vec![];
+    for record in records {
+        if let Action::Update(update) = record.action() {
+            if removed.contains(&update.original_action_address) {
+                removed.insert(record.action_address().clone());
+            }
+        }
+        if removed.contains(record.action_address()) {
+            continue;
+        }
+        let Some(content) = exported_content(&record)? else {
+            continue;
+        };
+        let previous_action_hash = match record.action() {
+            Action::Update(update) => Some(update.original_action_address.clone()),
+            _ => None,
+        };
+        exported
+            .push(ExportedRecord {
+                action_hash: record.action_address().clone(),
+                entry_hash: record
+                    .action()
+                    .entry_hash()
+                    .ok_or(
+                        wasm_error!(
+                            WasmErrorInner::Guest("Exported record has no entry hash"
+                            .to_string())
+                        ),
+                    )?
+                    .clone(),
+                timestamp: record.action().timestamp(),
+                previous_action_hash,
+                content,
+            });
+    }
+    Ok(exported)
+}
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct NotImportedRecord {
+    pub exported_action_hash: ActionHash,
+    pub reason: String,
+}
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ImportResult {
+    pub imported: Vec<ImportedRecord>,
+    pub not_imported: Vec<NotImportedRecord>,
+    // How many records at the end of the batch were left untouched because creating them would
+    // have exceeded the rate limit. They can be imported in a later batch, once it has passed
+    pub remaining: usize,
+}
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ImportContentInput {
+    pub records: Vec<ExportedRecord>,
+    // Everything that earlier batches of the same export imported, so that references to those
+    // records are remapped too
+    pub previously_imported: Vec<ImportedRecord>,
+}
+#[derive(Default)]
+struct HashMapping {
+    actions: HashMap<ActionHash, ActionHash>,
+    entries: HashMap<EntryHash, EntryHash>,
+    originals: HashMap<ActionHash, ActionHash>,
+}
+// References that can't be remapped point to records of the old DNA, which don't exist in
+// this one, so the records holding them are skipped instead of failing the whole import
+impl HashMapping {
+    fn action(&self, hash: &ActionHash) -> Result<ActionHash, String> {
+        self.actions
+            .get(hash)
+            .cloned()
+            .ok_or(format!("References record {}, which was not imported", hash))
+    }
+    fn entry(&self, hash: &EntryHash) -> Result<EntryHash, String> {
+        self.entries
+            .get(hash)
+            .cloned()
+            .ok_or(format!("References entry {}, which was not imported", hash))
+    }
+    fn original(&self, hash: &ActionHash) -> ActionHash {
+        self.originals.get(hash).cloned().unwrap_or(hash.clone())
+    }
+    fn insert(&mut self, imported: &ImportedRecord) {
+        self.actions
+            .insert(imported.exported_action_hash.clone(), imported.action_hash.clone());
+        self.entries.insert(imported.exported_entry_hash.clone(), imported.entry_hash.clone());
+        if let Some(previous) = &imported.exported_previous_action_hash {
+            self.originals
+                .insert(imported.exported_action_hash.clone(), self.original(previous));
+        }
+    }
+}
+// How many more Posts and Comments the agent can create right now without exceeding the rate
+// limit, which validation would otherwise reject along with the whole batch
+fn creates_left() -> ExternResult<usize> {
+    let Some(rate_limit) = posts_properties()?.rate_limit else {
+        return Ok(usize::MAX);
+    };
+    let window_start = (sys_time()? - std::time::Duration::from_secs(rate_limit.window_secs))
+        .unwrap_or(Timestamp::from_micros(0));
+    let mut recent_creates = 0;
+    for entry_type in [UnitEntryTypes::Post, UnitEntryTypes::Comment] {
+        recent_creates += query(
+                ChainQueryFilter::new()
+                    .entry_type(entry_type.try_into()?)
+                    .action_type(ActionType::Create),
+            )?
+            .iter()
+            .filter(|record| record.action().timestamp() >= window_start)
+            .count();
+    }
+    Ok(rate_limit.max_creates.saturating_sub(recent_creates))
+}
+fn is_rate_limited(exported: &ExportedRecord) -> bool {
+    exported.previous_action_hash.is_none()
+        && matches!(
+            exported.content, ExportedContent::Post(_) | ExportedContent::Comment(_)
+        )
+}
+// The content of an exported record and the hashes of the original and previous revisions it
+// updates, with every reference remapped to the imported records
+fn remap_record(
+    exported: &ExportedRecord,
+    mapping: &HashMapping,
+    dna_hash: &DnaHash,
+) -> Result<(ExportedContent, Option<(ActionHash, ActionHash)>), String> {
+    let revision = match &exported.previous_action_hash {
+        Some(previous) => {
+            Some((mapping.action(&mapping.original(previous))?, mapping.action(previous)?))
+        }
+        None => None,
+    };
+    let content = match exported.content.clone() {
+        ExportedContent::Post(post) => ExportedContent::Post(post),
+        ExportedContent::Comment(comment) => {
+            ExportedContent::Comment(Comment {
+                post_hash: mapping.action(&comment.post_hash)?,
+            })
+        }
+        ExportedContent::Like(like) => {
+            ExportedContent::Like(Like {
+                like_hash: like.like_hash.as_ref().map(|hash| mapping.action(hash)).transpose()?,
+                agent: like.agent,
+            })
+        }
+        ExportedContent::Certificate(certificate) => {
+            ExportedContent::Certificate(Certificate {
+                post_hash: mapping.action(&certificate.post_hash)?,
+                certifications_hashes: certificate
+                    .certifications_hashes
+                    .iter()
+                    .map(|hash| mapping.entry(hash))
+                    .collect::<Result<Vec<EntryHash>, String>>()?,
+                dna_hash: dna_hash.clone(),
+                ..certificate
+            })
+        }
+    };
+    Ok((content, revision))
+}
+fn create_imported_record(
+    content: ExportedContent,
+    revision: Option<(ActionHash, ActionHash)>,
+) -> ExternResult<Record> {
+    match (content, revision) {
+        (ExportedContent::Post(post), None) => crate::post::create_post(post),
+        (ExportedContent::Post(post), Some((original_post_hash, previous_post_hash))) => {
+            crate::post::update_post(UpdatePostInput {
+                original_post_hash,
+                previous_post_hash,
+                updated_post: post,
+            })
+        }
+        (ExportedContent::Comment(comment), None) => crate::comment::create_comment(comment),
+        (ExportedContent::Comment(comment), Some((_original, previous_comment_hash))) => {
+            crate::comment::update_comment(UpdateCommentInput {
+                previous_comment_hash,
+                updated_comment: comment,
+            })
+        }
+        (ExportedContent::Like(like), _) => crate::like::create_like(like),
+        (ExportedContent::Certificate(certificate), _) => {
+            crate::certificate::create_certificate(certificate)
+        }
+    }
+}
+// Recreates exported content in this DNA, in the given order. References to records that
+// are part of the import are remapped to their new hashes; records referencing anything else
+// are left out and listed with the reason. Certificates are issued for this DNA. Large exports
+// are imported in batches, each passing on what the earlier ones imported, and a batch stops
+// early when the rate limit is reached
+#[hdk_extern]
+pub fn import_content(input: ImportContentInput) -> ExternResult<ImportResult> {
+    let dna_hash = dna_info()?.hash;
+    let mut mapping = HashMapping::default();
+    for imported in input.previously_imported.iter() {
+        mapping.insert(imported);
+    }
+    let mut creates_left = creates_left()?;
+    let mut result = ImportResult {
+        imported: vec![],
+        not_imported: vec![],
+        remaining: 0,
+    };
+    let records_count = input.records.len();
+    for (index, exported) in input.records.into_iter().enumerate() {
+        match remap_record(&exported, &mapping, &dna_hash) {
+            Ok((content, revision)) => {
+                if is_rate_limited(&exported) {
+                    if creates_left == 0 {
+                        result.remaining = records_count - index;
+                        break;
+                    }
+                    creates_left -= 1;
+                }
+                let record = create_imported_record(content, revision)?;
+                let imported = ImportedRecord {
+                    exported_action_hash: exported.action_hash,
+                    action_hash: record.action_address().clone(),
+                    exported_entry_hash: exported.entry_hash,
+                    entry_hash: record
+                        .action()
+                        .entry_hash()
+                        .ok_or(
+                            wasm_error!(
+                                WasmErrorInner::Guest("Imported record has no entry hash"
+                                .to_string())
+                            ),
+                        )?
+                        .clone(),
+                    exported_previous_action_hash: exported.previous_action_hash,
+                };
+                mapping.insert(&imported);
+                result.imported.push(imported);
+            }
+            Err(reason) => {
+                result
+                    .not_imported
+                    .push(NotImportedRecord {
+                        exported_action_hash: exported.action_hash,
+                        reason,
+                    });
+            }
+        }
+    }
+    Ok(result)
+}
@@ -0,0 +1,67 @@
+use hdk::prelude::*;
+use posts_integrity::*;
+use crate::private_entries::{get_live_record, live_records};
+#[hdk_extern]
+pub fn save_draft(post: Post) -> ExternResult<Record> {
+    let draft_hash = create_entry(&EntryTypes::PostDraft(PostDraft { post }))?;
+    get_live_record(UnitEntryTypes::PostDraft, &draft_hash)?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("Could not find the newly created PostDraft"
+                .to_string())
+            ),
+        )
+}
+#[hdk_extern]
+pub fn list_drafts() -> ExternResult<Vec<Record>> {
+    live_records(UnitEntryTypes::PostDraft)
+}
+#[derive(Serialize, Deserialize, Debug)]
+pub struct UpdateDraftInput {
+    pub previous_draft_hash: ActionHash,
+    pub updated_post: Post,
+}
+#[hdk_extern]
+pub fn update_draft(input: UpdateDraftInput) -> ExternResult<Record> {
+    if get_live_record(UnitEntryTypes::PostDraft, &input.previous_draft_hash)?.is_none() {
+        return Err(
+            wasm_error!(
+                WasmErrorInner::Guest("PostDraft not found, or already updated or published"
+                .to_string())
+            ),
+        );
+    }
+    let updated_draft_hash = update_entry(
+        input.previous_draft_hash,
+        &PostDraft {
+            post: input.updated_post,
+        },
+    )?;
+    get_live_record(UnitEntryTypes::PostDraft, &updated_draft_hash)?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("Could not find the newly updated PostDraft"
+                .to_string())
+            ),
+        )
+}
+#[hdk_extern]
+pub fn publish_draft(draft_hash: ActionHash) -> ExternResult<Record> {
+    let record = get_live_record(UnitEntryTypes::PostDraft, &draft_hash)?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("PostDraft not found or already published"
+                .to_string())
+            ),
+        )?;
+    let draft: PostDraft = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(
+            wasm_error!(WasmErrorInner::Guest("PostDraft record has no entry".to_string())),
+        )?;
+    let post_record = crate::post::create_post(draft.post)?;
+    delete_entry(draft_hash)?;
+    Ok(post_record)
+}
@@ -0,0 +1,41 @@
+use hdk::prelude::*;
+use posts_integrity::*;
+pub fn live_records(entry_type: UnitEntryTypes) -> ExternResult<Vec<Record>> {
+    let records = query(
+        ChainQueryFilter::new().entry_type(entry_type.try_into()?).include_entries(true),
+    )?;
+    let mut removed_hashes: Vec<ActionHash> = query(
+            ChainQueryFilter::new().action_type(ActionType::Delete),
+        )?
+        .into_iter()
+        .filter_map(|record| match record.action() {
+            Action::Delete(delete) => Some(delete.deletes_address.clone()),
+            _ => None,
+        })
+        .collect();
+    removed_hashes
+        .extend(
+            records
+                .iter()
+                .filter_map(|record| match record.action() {
+                    Action::Update(update) => Some(update.original_action_address.clone()),
+                    _ => None,
+                }),
+        );
+    Ok(
+        records
+            .into_iter()
+            .filter(|record| !removed_hashes.contains(record.action_address()))
+            .collect(),
+    )
+}
+pub fn get_live_record(
+    entry_type: UnitEntryTypes,
+    action_hash: &ActionHash,
+) -> ExternResult<Option<Record>> {
+    Ok(
+        live_records(entry_type)?
+            .into_iter()
+            .find(|record| record.action_address().eq(action_hash)),
+    )
+}
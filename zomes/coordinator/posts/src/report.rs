@@ -0,0 +1,113 @@
+use std::collections::{BTreeMap, BTreeSet};
+use hdk::prelude::*;
+use posts_integrity::*;
+use crate::pagination::{paginate, Page};
+#[hdk_extern]
+pub fn create_report(report: Report) -> ExternResult<Record> {
+    let target_record = get(report.target_hash.clone(), GetOptions::default())?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("Could not find the reported item".to_string())
+            ),
+        )?;
+    let post_entry_type: EntryType = UnitEntryTypes::Post.try_into()?;
+    let comment_entry_type: EntryType = UnitEntryTypes::Comment.try_into()?;
+    let link_type = match target_record.action().entry_type() {
+        Some(entry_type) if entry_type.eq(&post_entry_type) => LinkTypes::PostToReports,
+        Some(entry_type) if entry_type.eq(&comment_entry_type) => LinkTypes::CommentToReports,
+        _ => {
+            return Err(
+                wasm_error!(
+                    WasmErrorInner::Guest("Only posts and comments can be reported"
+                    .to_string())
+                ),
+            );
+        }
+    };
+    if my_reports()?.iter().any(|my_report| my_report.target_hash.eq(&report.target_hash)) {
+        return Err(
+            wasm_error!(
+                WasmErrorInner::Guest("An agent can only report the same item once"
+                .to_string())
+            ),
+        );
+    }
+    let report_hash = create_entry(&EntryTypes::Report(report.clone()))?;
+    create_link(report.target_hash.clone(), report_hash.clone(), link_type, ())?;
+    let path = Path::from("reported_items");
+    create_link(
+        path.path_entry_hash()?,
+        report.target_hash.clone(),
+        LinkTypes::ReportedItems,
+        LinkTag::new(ExternIO::encode(report_hash.clone()).map_err(|e| wasm_error!(e))?.0),
+    )?;
+    let record = get(report_hash.clone(), GetOptions::default())?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("Could not find the newly created Report"
+                .to_string())
+            ),
+        )?;
+    Ok(record)
+}
+fn my_reports() -> ExternResult<Vec<Report>> {
+    let records = query(
+        ChainQueryFilter::new()
+            .entry_type(UnitEntryTypes::Report.try_into()?)
+            .include_entries(true),
+    )?;
+    records
+        .into_iter()
+        .filter_map(|record| record.entry().to_app_option::<Report>().transpose())
+        .collect::<Result<Vec<Report>, _>>()
+        .map_err(|e| wasm_error!(e))
+}
+// Returns the first report of each agent, like get_most_reported counts them
+#[hdk_extern]
+pub fn get_reports_for(target_hash: ActionHash) -> ExternResult<Vec<Link>> {
+    let mut links = get_links(
+        GetLinksInputBuilder::try_new(target_hash.clone(), LinkTypes::PostToReports)?
+            .build(),
+    )?;
+    links
+        .extend(
+            get_links(
+                GetLinksInputBuilder::try_new(target_hash, LinkTypes::CommentToReports)?
+                    .build(),
+            )?,
+        );
+    links.sort_by_key(|link| link.timestamp);
+    let mut reporters = BTreeSet::new();
+    links.retain(|link| reporters.insert(link.author.clone()));
+    Ok(links)
+}
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ReportedItem {
+    pub target_hash: ActionHash,
+    pub reports_count: usize,
+}
+#[hdk_extern]
+pub fn get_most_reported(page: Page) -> ExternResult<Vec<ReportedItem>> {
+    let path = Path::from("reported_items");
+    let links = get_links(
+        GetLinksInputBuilder::try_new(path.path_entry_hash()?, LinkTypes::ReportedItems)?
+            .build(),
+    )?;
+    // Each agent counts once per item, even if they managed to link more than one Report to it
+    let mut reporters: BTreeMap<ActionHash, BTreeSet<AgentPubKey>> = BTreeMap::new();
+    for link in links {
+        if let Some(target_hash) = link.target.into_action_hash() {
+            reporters.entry(target_hash).or_default().insert(link.author);
+        }
+    }
+    let mut reported_items: Vec<ReportedItem> = reporters
+        .into_iter()
+        .map(|(target_hash, reporters)| ReportedItem {
+            target_hash,
+            reports_count: reporters.len(),
+        })
+        .collect();
+    reported_items
+        .sort_by(|item_a, item_b| item_b.reports_count.cmp(&item_a.reports_count));
+    Ok(paginate(reported_items, &page))
+}
@@ -0,0 +1,165 @@
+use hdk::prelude::*;
+use posts_integrity::*;
+use crate::private_entries::{get_live_record, live_records};
+#[derive(Serialize, Deserialize, Debug)]
+pub struct SchedulePostInput {
+    pub post: Post,
+    pub publish_at: Timestamp,
+}
+// Rejects posts that would fail validation once they are published, so that they are not
+// retried by every run of the scheduler
+fn check_schedulable(post: &Post, publish_at: Timestamp) -> ExternResult<()> {
+    let properties = posts_properties()?;
+    if let Some(error) = check_post_fields(post, &properties.post_limits) {
+        return Err(wasm_error!(WasmErrorInner::Guest(error)));
+    }
+    let Some(rate_limit) = properties.rate_limit else {
+        return Ok(());
+    };
+    let window_micros = (rate_limit.window_secs as i64).saturating_mul(1_000_000);
+    let mut scheduled_in_window = 0;
+    for record in live_records(UnitEntryTypes::ScheduledPost)? {
+        let Some(scheduled_post) = record
+            .entry()
+            .to_app_option::<ScheduledPost>()
+            .map_err(|e| wasm_error!(e))? else {
+            continue;
+        };
+        let distance = scheduled_post.publish_at.as_micros() - publish_at.as_micros();
+        if distance.abs() < window_micros {
+            scheduled_in_window += 1;
+        }
+    }
+    if scheduled_in_window >= rate_limit.max_creates {
+        return Err(
+            wasm_error!(
+                WasmErrorInner::Guest(
+                    format!(
+                        "Rate limit exceeded: at most {} posts can be scheduled within {} seconds of each other",
+                        rate_limit.max_creates, rate_limit.window_secs
+                    ),
+                )
+            ),
+        );
+    }
+    Ok(())
+}
+#[hdk_extern]
+pub fn schedule_post(input: SchedulePostInput) -> ExternResult<Record> {
+    check_schedulable(&input.post, input.publish_at)?;
+    let scheduled_post_hash = create_entry(
+        &EntryTypes::ScheduledPost(ScheduledPost {
+            post: input.post,
+            publish_at: input.publish_at,
+        }),
+    )?;
+    schedule("publish_scheduled_posts")?;
+    get_live_record(UnitEntryTypes::ScheduledPost, &scheduled_post_hash)?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("Could not find the newly created ScheduledPost"
+                .to_string())
+            ),
+        )
+}
+#[hdk_extern]
+pub fn get_scheduled_posts() -> ExternResult<Vec<Record>> {
+    live_records(UnitEntryTypes::ScheduledPost)
+}
+fn get_live_scheduled_post(scheduled_post_hash: &ActionHash) -> ExternResult<Record> {
+    get_live_record(UnitEntryTypes::ScheduledPost, scheduled_post_hash)?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest(format!("{} is not a pending ScheduledPost",
+                scheduled_post_hash))
+            ),
+        )
+}
+#[hdk_extern]
+pub fn cancel_scheduled_post(scheduled_post_hash: ActionHash) -> ExternResult<ActionHash> {
+    get_live_scheduled_post(&scheduled_post_hash)?;
+    delete_entry(scheduled_post_hash)
+}
+// Publishes a ScheduledPost right away. The scheduler calls it once per due post, so that each
+// post is committed in a zome call of its own and one that fails cannot hold back the others
+#[hdk_extern]
+pub fn publish_scheduled_post(scheduled_post_hash: ActionHash) -> ExternResult<Record> {
+    let record = get_live_scheduled_post(&scheduled_post_hash)?;
+    let scheduled_post: ScheduledPost = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("ScheduledPost record has no entry".to_string())
+            ),
+        )?;
+    let post_record = crate::post::create_post(scheduled_post.post)?;
+    delete_entry(scheduled_post_hash)?;
+    Ok(post_record)
+}
+// Returns how many posts are still pending, including those that failed to publish, which are
+// retried on the next run
+fn publish_due_posts() -> ExternResult<usize> {
+    let now = sys_time()?;
+    let mut due = vec![];
+    let mut pending = 0;
+    for record in live_records(UnitEntryTypes::ScheduledPost)? {
+        let Some(scheduled_post) = record
+            .entry()
+            .to_app_option::<ScheduledPost>()
+            .map_err(|e| wasm_error!(e))? else {
+            continue;
+        };
+        if scheduled_post.publish_at > now {
+            pending += 1;
+            continue;
+        }
+        due.push((scheduled_post.publish_at, record.action_address().clone()));
+    }
+    due.sort();
+    let zome_name = zome_info()?.name;
+    for (_, scheduled_post_hash) in due {
+        let response = call(
+            CallTargetCell::Local,
+            zome_name.clone(),
+            "publish_scheduled_post".into(),
+            None,
+            scheduled_post_hash.clone(),
+        );
+        if !matches!(response, Ok(ZomeCallResponse::Ok(_))) {
+            error!(
+                "Error publishing scheduled post {}: {:?}", scheduled_post_hash, response
+            );
+            pending += 1;
+        }
+    }
+    Ok(pending)
+}
+#[hdk_extern(infallible)]
+pub fn publish_scheduled_posts(_: Option<Schedule>) -> Option<Schedule> {
+    let pending = match publish_due_posts() {
+        Ok(pending) => pending,
+        Err(err) => {
+            error!("Error publishing scheduled posts: {:?}", err);
+            1
+        }
+    };
+    if pending == 0 {
+        return None;
+    }
+    let interval_secs = posts_properties()
+        .map(|properties| properties.scheduled_posts_interval_secs)
+        .unwrap_or(PostsProperties::default().scheduled_posts_interval_secs)
+        .max(1);
+    Some(publish_schedule(interval_secs))
+}
+// Persisted, so that pending posts are still published after the conductor restarts
+fn publish_schedule(interval_secs: u64) -> Schedule {
+    let cron = if interval_secs < 60 {
+        format!("*/{} * * * * * *", interval_secs)
+    } else {
+        format!("0 */{} * * * * *", (interval_secs / 60).min(59))
+    };
+    Schedule::Persisted(cron)
+}
@@ -0,0 +1,122 @@
+use hdk::prelude::*;
+use posts_integrity::*;
+use crate::private_entries::live_records;
+use crate::Signal;
+fn live_subscription_records() -> ExternResult<Vec<Record>> {
+    live_records(UnitEntryTypes::Subscription)
+}
+// Runs on every commit, so it only walks the Subscription actions and the deletes after the
+// latest one instead of the whole chain
+pub fn current_subscription() -> ExternResult<Option<Subscription>> {
+    let Some(record) = query(
+            ChainQueryFilter::new()
+                .entry_type(UnitEntryTypes::Subscription.try_into()?)
+                .include_entries(true)
+                .descending(),
+        )?
+        .into_iter()
+        .next() else {
+        return Ok(None);
+    };
+    let deletes = query(
+        ChainQueryFilter::new()
+            .sequence_range(
+                ChainQueryFilterRange::ActionSeqRange(record.action().action_seq(), u32::MAX),
+            )
+            .action_type(ActionType::Delete),
+    )?;
+    let deleted = deletes
+        .iter()
+        .any(|delete| match delete.action() {
+            Action::Delete(delete) => delete.deletes_address.eq(record.action_address()),
+            _ => false,
+        });
+    if deleted {
+        return Ok(None);
+    }
+    record.entry().to_app_option().map_err(|e| wasm_error!(e))
+}
+#[hdk_extern]
+pub fn get_subscription() -> ExternResult<Option<Subscription>> {
+    current_subscription()
+}
+#[hdk_extern]
+pub fn subscribe(subscription: Subscription) -> ExternResult<ActionHash> {
+    match live_subscription_records()?.pop() {
+        Some(record) => update_entry(record.action_address().clone(), &subscription),
+        None => create_entry(&EntryTypes::Subscription(subscription)),
+    }
+}
+#[hdk_extern]
+pub fn unsubscribe() -> ExternResult<()> {
+    for record in live_subscription_records()? {
+        delete_entry(record.action_address().clone())?;
+    }
+    Ok(())
+}
+fn entry_references(app_entry: &EntryTypes) -> Vec<ActionHash> {
+    match app_entry {
+        EntryTypes::Comment(comment) => vec![comment.post_hash.clone()],
+        EntryTypes::Certificate(certificate) => vec![certificate.post_hash.clone()],
+        EntryTypes::ModerationAction(moderation_action) => {
+            vec![moderation_action.target_hash.clone()]
+        }
+        EntryTypes::Report(report) => vec![report.target_hash.clone()],
+        _ => vec![],
+    }
+}
+fn action_references(action: &SignedActionHashed) -> Vec<ActionHash> {
+    let mut hashes = vec![action.action_address().clone()];
+    match action.action() {
+        Action::Update(update) => hashes.push(update.original_action_address.clone()),
+        Action::Delete(delete) => hashes.push(delete.deletes_address.clone()),
+        Action::CreateLink(create_link) => {
+            hashes.extend(create_link.base_address.clone().into_action_hash());
+            hashes.extend(create_link.target_address.clone().into_action_hash());
+        }
+        _ => {}
+    }
+    hashes
+}
+pub fn signal_matches(signal: &Signal, subscription: &Subscription) -> bool {
+    let (entry_type, link_type, references) = match signal {
+        Signal::LinkCreated { action, link_type } => {
+            (None, Some(link_type), action_references(action))
+        }
+        Signal::LinkDeleted { create_link_action, link_type, .. } => {
+            (None, Some(link_type), action_references(create_link_action))
+        }
+        Signal::EntryCreated { action, app_entry } => {
+            let mut references = action_references(action);
+            references.extend(entry_references(app_entry));
+            (Some(app_entry.to_unit()), None, references)
+        }
+        Signal::EntryUpdated { action, app_entry, .. } => {
+            let mut references = action_references(action);
+            references.extend(entry_references(app_entry));
+            (Some(app_entry.to_unit()), None, references)
+        }
+        Signal::EntryDeleted { action, original_app_entry } => {
+            let mut references = action_references(action);
+            references.extend(entry_references(original_app_entry));
+            (Some(original_app_entry.to_unit()), None, references)
+        }
+        Signal::Notification { .. } => return true,
+    };
+    if let (Some(entry_type), Some(entry_types)) = (entry_type, &subscription.entry_types) {
+        if !entry_types.contains(&entry_type) {
+            return false;
+        }
+    }
+    if let (Some(link_type), Some(link_types)) = (link_type, &subscription.link_types) {
+        if !link_types.contains(link_type) {
+            return false;
+        }
+    }
+    if let Some(post_hashes) = &subscription.post_hashes {
+        if !references.iter().any(|hash| post_hashes.contains(hash)) {
+            return false;
+        }
+    }
+    true
+}
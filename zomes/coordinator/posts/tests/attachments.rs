@@ -0,0 +1,144 @@
+#![allow(dead_code)]
+#![allow(unused_variables)]
+#![allow(unused_imports)]
+
+use hdk::prelude::*;
+use holochain::sweettest::*;
+use std::time::Duration;
+
+use posts_integrity::*;
+
+use file_storage::{File, GetFileChunkInput};
+use file_storage_integrity::{FileChunk, FileManifest, MAX_CHUNK_BYTES};
+
+mod common;
+use common::{assert_rejected, create_post, sample_post_1, setup};
+
+fn file_chunk(bytes: &[u8]) -> FileChunk {
+    FileChunk(SerializedBytes::from(UnsafeBytes::from(bytes.to_vec())))
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn post_with_a_file_attachment() {
+    // Set up conductors
+    let (conductors, alice, bobbo) = setup().await;
+
+    let alice_zome = alice.zome("posts");
+    let alice_files_zome = alice.zome("file_storage");
+    let bob_files_zome = bobbo.zome("file_storage");
+
+    // Alice uploads a file that spans two chunks
+    let bytes: Vec<u8> = (0..MAX_CHUNK_BYTES + 10).map(|i| i as u8).collect();
+    let mut chunks = vec![];
+    for chunk in bytes.chunks(MAX_CHUNK_BYTES) {
+        let chunk_hash: EntryHash = conductors[0]
+            .call(&alice_files_zome, "upload_file_chunk", file_chunk(chunk))
+            .await;
+        chunks.push(chunk_hash);
+    }
+    let manifest = FileManifest {
+        name: "image.png".to_string(),
+        mime_type: "image/png".to_string(),
+        size: bytes.len(),
+        chunks,
+    };
+    let manifest_hash: ActionHash = conductors[0]
+        .call(&alice_files_zome, "create_file_manifest", manifest.clone())
+        .await;
+
+    // Alice attaches the file to a Post
+    let post = Post {
+        body: "Look at this ![image](attachment:0)".to_string(),
+        format: ContentFormat::Markdown,
+        attachments: vec![manifest_hash.clone()],
+        ..sample_post_1(&conductors[0], &alice_zome).await
+    };
+    let record = create_post(&conductors[0], &alice_zome, post.clone()).await;
+
+    await_consistency(Duration::from_secs(60), [&alice, &bobbo])
+        .await
+        .expect("Timed out waiting for consistency");
+
+    // Bob gets the Post and downloads its attachment
+    let bob_zome = bobbo.zome("posts");
+    let latest: Option<Record> = conductors[1]
+        .call(&bob_zome, "get_latest_post", record.signed_action.hashed.hash.clone())
+        .await;
+    let latest_post: Post = latest.unwrap().entry().to_app_option().unwrap().unwrap();
+    assert_eq!(latest_post, post);
+
+    let file: Option<File> = conductors[1]
+        .call(&bob_files_zome, "get_file", latest_post.attachments[0].clone())
+        .await;
+    let file = file.unwrap();
+    assert_eq!(file.manifest, manifest);
+    assert_eq!(file.bytes.bytes(), &bytes);
+
+    // Bob can also stream the file chunk by chunk
+    let streamed_manifest: Option<FileManifest> = conductors[1]
+        .call(&bob_files_zome, "get_file_manifest", manifest_hash.clone())
+        .await;
+    let streamed_manifest = streamed_manifest.unwrap();
+    assert_eq!(streamed_manifest, manifest);
+    let mut streamed_bytes = vec![];
+    for index in 0..streamed_manifest.chunks.len() {
+        let chunk: Option<FileChunk> = conductors[1]
+            .call(
+                &bob_files_zome,
+                "get_file_chunk",
+                GetFileChunkInput {
+                    file_manifest_hash: manifest_hash.clone(),
+                    index,
+                },
+            )
+            .await;
+        streamed_bytes.extend_from_slice(chunk.unwrap().0.bytes());
+    }
+    assert_eq!(streamed_bytes, bytes);
+
+    let out_of_range: Result<Option<FileChunk>, _> = conductors[1]
+        .call_fallible(
+            &bob_files_zome,
+            "get_file_chunk",
+            GetFileChunkInput {
+                file_manifest_hash: manifest_hash.clone(),
+                index: 2,
+            },
+        )
+        .await;
+    assert!(out_of_range.is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn file_manifest_must_match_its_chunks() {
+    // Set up conductors
+    let (conductors, alice, bobbo) = setup().await;
+
+    let alice_files_zome = alice.zome("file_storage");
+
+    let chunk_hash: EntryHash = conductors[0]
+        .call(&alice_files_zome, "upload_file_chunk", file_chunk(b"Hello"))
+        .await;
+
+    let result: Result<ActionHash, _> = conductors[0]
+        .call_fallible(
+            &alice_files_zome,
+            "create_file_manifest",
+            FileManifest {
+                name: "hello.txt".to_string(),
+                mime_type: "text/plain".to_string(),
+                size: 6,
+                chunks: vec![chunk_hash],
+            },
+        )
+        .await;
+    assert_rejected(
+        result,
+        "FileManifest declares a size of 6 bytes, but its chunks add up to 5",
+    );
+
+    let result: Result<EntryHash, _> = conductors[0]
+        .call_fallible(&alice_files_zome, "upload_file_chunk", file_chunk(&[]))
+        .await;
+    assert_rejected(result, "FileChunks cannot be empty");
+}
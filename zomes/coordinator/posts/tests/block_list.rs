@@ -0,0 +1,102 @@
+#![allow(dead_code)]
+#![allow(unused_variables)]
+#![allow(unused_imports)]
+
+use hdk::prelude::*;
+use holochain::sweettest::*;
+use std::time::Duration;
+
+use posts_integrity::*;
+
+mod common;
+use common::{create_comment, create_like, create_post, sample_like_1, sample_post_1, sample_post_2, setup};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn blocked_agents_are_filtered_out_and_muted_agents_only_from_feeds() {
+    // Set up conductors
+    let (conductors, alice, bobbo) = setup().await;
+
+    let alice_zome = alice.zome("posts");
+    let bob_zome = bobbo.zome("posts");
+
+    // Alice and Bob each create a Post, Bob comments on Alice's Post and likes Alice's Like
+    let alice_post = create_post(&conductors[0], &alice_zome, sample_post_1(&conductors[0], &alice_zome).await).await;
+    let alice_post_hash = alice_post.signed_action.hashed.hash.clone();
+    create_post(&conductors[1], &bob_zome, sample_post_2(&conductors[1], &bob_zome).await).await;
+    let alice_like = create_like(&conductors[0], &alice_zome, sample_like_1(&conductors[0], &alice_zome).await).await;
+    let alice_like_hash = alice_like.signed_action.hashed.hash.clone();
+
+    await_consistency(Duration::from_secs(60), [&alice, &bobbo])
+        .await
+        .expect("Timed out waiting for consistency");
+
+    create_comment(
+        &conductors[1],
+        &bob_zome,
+        Comment {
+            post_hash: alice_post_hash.clone(),
+        },
+    )
+    .await;
+    create_like(
+        &conductors[1],
+        &bob_zome,
+        Like {
+            like_hash: Some(alice_like_hash.clone()),
+            agent: alice.agent_pubkey().clone(),
+        },
+    )
+    .await;
+
+    await_consistency(Duration::from_secs(60), [&alice, &bobbo])
+        .await
+        .expect("Timed out waiting for consistency");
+
+    let assert_visible = |posts: usize, comments: usize, likes: usize| {
+        let conductor = &conductors[0];
+        let alice_zome = &alice_zome;
+        let alice_post_hash = alice_post_hash.clone();
+        let alice_like_hash = alice_like_hash.clone();
+        async move {
+            let links: Vec<Link> = conductor.call(alice_zome, "get_all_posts", ()).await;
+            assert_eq!(links.len(), posts);
+            let links: Vec<Link> = conductor
+                .call(alice_zome, "get_comments_for_post", alice_post_hash)
+                .await;
+            assert_eq!(links.len(), comments);
+            let links: Vec<Link> = conductor
+                .call(alice_zome, "get_likes_for_like", alice_like_hash)
+                .await;
+            assert_eq!(links.len(), likes);
+        }
+    };
+
+    assert_visible(2, 1, 1).await;
+
+    // Alice blocks Bob, which takes effect immediately
+    let block_list: BlockList = conductors[0]
+        .call(&alice_zome, "block_agent", bobbo.agent_pubkey().clone())
+        .await;
+    assert_eq!(block_list.blocked_agents, vec![bobbo.agent_pubkey().clone()]);
+    assert_visible(1, 0, 0).await;
+
+    // Alice unblocks Bob but mutes him
+    let _: BlockList = conductors[0]
+        .call(&alice_zome, "unblock_agent", bobbo.agent_pubkey().clone())
+        .await;
+    assert_visible(2, 1, 1).await;
+    let _: BlockList = conductors[0]
+        .call(&alice_zome, "mute_agent", bobbo.agent_pubkey().clone())
+        .await;
+    // Bob's Post is hidden from the feed, but his Comment and Like on Alice's content are not
+    assert_visible(1, 1, 1).await;
+
+    let _: BlockList = conductors[0]
+        .call(&alice_zome, "unmute_agent", bobbo.agent_pubkey().clone())
+        .await;
+    assert_visible(2, 1, 1).await;
+
+    // Bob's view is unaffected by Alice's block list
+    let links: Vec<Link> = conductors[1].call(&bob_zome, "get_all_posts", ()).await;
+    assert_eq!(links.len(), 2);
+}
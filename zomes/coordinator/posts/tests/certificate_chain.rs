@@ -0,0 +1,164 @@
+#![allow(dead_code)]
+#![allow(unused_variables)]
+#![allow(unused_imports)]
+
+use hdk::prelude::*;
+use holochain::prelude::DatabaseResult;
+use holochain::sweettest::*;
+use std::time::Duration;
+
+use posts_integrity::*;
+
+mod common;
+use common::{create_certificate, create_post, sample_post_1, setup};
+
+async fn count_dht_ops(conductor: &SweetConductor, cell: &SweetCell, sql: &'static str) -> i64 {
+    let db = conductor.get_dht_db(cell.cell_id().dna_hash()).unwrap();
+    db.read_async(move |txn| -> DatabaseResult<i64> {
+        Ok(txn.query_row(sql, (), |row| row.get(0))?)
+    })
+    .await
+    .unwrap()
+}
+
+async fn await_validation_receipts(conductor: &SweetConductor, cell: &SweetCell) {
+    tokio::time::timeout(Duration::from_secs(60), async {
+        while count_dht_ops(conductor, cell, "SELECT COUNT(*) FROM ValidationReceipt").await == 0 {
+            tokio::time::sleep(Duration::from_millis(500)).await;
+        }
+    })
+    .await
+    .expect("Timed out waiting for validation receipts");
+}
+
+// Certified agents record a notification when they receive the remote signal for their
+// certificate, which commits ops of their own after the certifier's call has returned
+async fn await_unread_notifications(conductor: &SweetConductor, zome: &SweetZome, count: usize) {
+    tokio::time::timeout(Duration::from_secs(60), async {
+        loop {
+            let unread_count: usize = conductor.call(zome, "get_unread_count", ()).await;
+            if unread_count >= count {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(500)).await;
+        }
+    })
+    .await
+    .expect("Timed out waiting for notifications");
+}
+
+fn certificate(post_hash: &ActionHash, agent: &SweetCell, certifications_hashes: Vec<EntryHash>) -> Certificate {
+    Certificate {
+        post_hash: post_hash.clone(),
+        agent: agent.agent_pubkey().clone(),
+        certifications_hashes,
+        certificate_type: CertificateType::TypeOne,
+        dna_hash: agent.cell_id().dna_hash().clone(),
+    }
+}
+
+fn entry_hash(record: &Record) -> EntryHash {
+    record.action().entry_hash().unwrap().clone()
+}
+
+// Certificates that reference certificates authored on the other conductor, whose
+// CertificateToCertificates links have an entry hash as their base
+#[tokio::test(flavor = "multi_thread")]
+async fn certificate_chains_across_conductors_are_validated_and_integrated() {
+    // Set up conductors
+    let (conductors, alice, bobbo) = setup().await;
+
+    let alice_zome = alice.zome("posts");
+    let bob_zome = bobbo.zome("posts");
+
+    // Alice creates a Post and certifies Bob for it
+    let post_record = create_post(&conductors[0], &alice_zome, sample_post_1(&conductors[0], &alice_zome).await).await;
+    let post_hash = post_record.signed_action.hashed.hash.clone();
+    let certificate_a = create_certificate(&conductors[0], &alice_zome, certificate(&post_hash, &bobbo, vec![])).await;
+
+    await_consistency(Duration::from_secs(60), [&alice, &bobbo])
+        .await
+        .expect("Timed out waiting for consistency after the first certificate");
+
+    // Bob certifies Alice, referencing the certificate that Alice issued him
+    let certificate_b = create_certificate(
+        &conductors[1],
+        &bob_zome,
+        certificate(&post_hash, &alice, vec![entry_hash(&certificate_a)]),
+    )
+    .await;
+
+    await_consistency(Duration::from_secs(60), [&alice, &bobbo])
+        .await
+        .expect("Timed out waiting for consistency after the second certificate");
+
+    // Alice certifies Bob again, referencing both previous certificates
+    let certificate_c = create_certificate(
+        &conductors[0],
+        &alice_zome,
+        certificate(
+            &post_hash,
+            &bobbo,
+            vec![entry_hash(&certificate_a), entry_hash(&certificate_b)],
+        ),
+    )
+    .await;
+
+    await_unread_notifications(&conductors[0], &alice_zome, 1).await;
+    await_unread_notifications(&conductors[1], &bob_zome, 2).await;
+    await_consistency(Duration::from_secs(60), [&alice, &bobbo])
+        .await
+        .expect("Timed out waiting for consistency after the third certificate");
+
+    // Every op was integrated as valid on both conductors, and both hold the same ops
+    for (conductor, cell) in [(&conductors[0], &alice), (&conductors[1], &bobbo)] {
+        assert_eq!(
+            count_dht_ops(conductor, cell, "SELECT COUNT(*) FROM DhtOp WHERE when_integrated IS NULL").await,
+            0,
+            "All ops should have been integrated"
+        );
+        assert_eq!(
+            count_dht_ops(conductor, cell, "SELECT COUNT(*) FROM DhtOp WHERE validation_status != 0").await,
+            0,
+            "No op should have been rejected or abandoned"
+        );
+    }
+    let integrated_ops = "SELECT COUNT(*) FROM DhtOp WHERE when_integrated IS NOT NULL";
+    assert_eq!(
+        count_dht_ops(&conductors[0], &alice, integrated_ops).await,
+        count_dht_ops(&conductors[1], &bobbo, integrated_ops).await,
+    );
+
+    // Each author got validation receipts for their ops from the other conductor
+    await_validation_receipts(&conductors[0], &alice).await;
+    await_validation_receipts(&conductors[1], &bobbo).await;
+
+    // The certificate chain can be followed from either conductor
+    for (conductor, zome) in [(&conductors[0], &alice_zome), (&conductors[1], &bob_zome)] {
+        let links: Vec<Link> = conductor
+            .call(zome, "get_certificates_for_certificate", entry_hash(&certificate_a))
+            .await;
+        let mut targets: Vec<AnyLinkableHash> = links.into_iter().map(|link| link.target).collect();
+        targets.sort();
+        let mut expected: Vec<AnyLinkableHash> = vec![
+            certificate_b.signed_action.hashed.hash.clone().into(),
+            certificate_c.signed_action.hashed.hash.clone().into(),
+        ];
+        expected.sort();
+        assert_eq!(targets, expected);
+
+        let links: Vec<Link> = conductor
+            .call(zome, "get_certificates_for_certificate", entry_hash(&certificate_b))
+            .await;
+        assert_eq!(links.len(), 1);
+        assert_eq!(
+            links[0].target,
+            certificate_c.signed_action.hashed.hash.clone().into()
+        );
+
+        let links: Vec<Link> = conductor
+            .call(zome, "get_certificates_for_post", post_hash.clone())
+            .await;
+        assert_eq!(links.len(), 3);
+    }
+}
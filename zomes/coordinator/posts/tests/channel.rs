@@ -0,0 +1,139 @@
+#![allow(dead_code)]
+#![allow(unused_variables)]
+#![allow(unused_imports)]
+
+use hdk::prelude::*;
+use holochain::sweettest::*;
+use std::time::Duration;
+
+use posts_integrity::*;
+
+use posts::channel::{AddPostToChannelInput, ChannelPostInput};
+
+mod common;
+use common::{assert_rejected, create_post, sample_post_1, sample_post_2, setup};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn posts_are_listed_per_channel() {
+    // Set up conductors
+    let (conductors, alice, bobbo) = setup().await;
+
+    let alice_zome = alice.zome("posts");
+    let bob_zome = bobbo.zome("posts");
+
+    // Alice creates a channel open to anyone and one for herself
+    let open: Record = conductors[0]
+        .call(
+            &alice_zome,
+            "create_channel",
+            Channel {
+                name: "General".to_string(),
+                description: "Anything goes".to_string(),
+                posting_policy: PostingPolicy::Anyone,
+            },
+        )
+        .await;
+    let open_hash = open.signed_action.hashed.hash.clone();
+    let members: Record = conductors[0]
+        .call(
+            &alice_zome,
+            "create_channel",
+            Channel {
+                name: "Alice".to_string(),
+                description: String::new(),
+                posting_policy: PostingPolicy::Members { members: vec![] },
+            },
+        )
+        .await;
+    let members_hash = members.signed_action.hashed.hash.clone();
+
+    // Alice posts to both, Bob only to the open one
+    let alice_post = create_post(&conductors[0], &alice_zome, sample_post_1(&conductors[0], &alice_zome).await).await;
+    let alice_post_hash = alice_post.signed_action.hashed.hash.clone();
+    for channel_hash in [&open_hash, &members_hash] {
+        let _: ActionHash = conductors[0]
+            .call(
+                &alice_zome,
+                "add_post_to_channel",
+                AddPostToChannelInput {
+                    channel_hash: channel_hash.clone(),
+                    post_hash: alice_post_hash.clone(),
+                    moderator_certificate_hash: None,
+                },
+            )
+            .await;
+    }
+
+    let bob_post = create_post(&conductors[1], &bob_zome, sample_post_2(&conductors[1], &bob_zome).await).await;
+    let bob_post_hash = bob_post.signed_action.hashed.hash.clone();
+    let _: ActionHash = conductors[1]
+        .call(
+            &bob_zome,
+            "add_post_to_channel",
+            AddPostToChannelInput {
+                channel_hash: open_hash.clone(),
+                post_hash: bob_post_hash.clone(),
+                moderator_certificate_hash: None,
+            },
+        )
+        .await;
+    let result: Result<ActionHash, _> = conductors[1]
+        .call_fallible(
+            &bob_zome,
+            "add_post_to_channel",
+            AddPostToChannelInput {
+                channel_hash: members_hash.clone(),
+                post_hash: bob_post_hash.clone(),
+                moderator_certificate_hash: None,
+            },
+        )
+        .await;
+    assert_rejected(
+        result,
+        "The posting policy of Channel \"Alice\" does not allow this agent to post to it",
+    );
+
+    await_consistency(Duration::from_secs(60), [&alice, &bobbo])
+        .await
+        .expect("Timed out waiting for consistency");
+
+    let links: Vec<Link> = conductors[1].call(&bob_zome, "list_channels", ()).await;
+    let mut channel_hashes: Vec<AnyLinkableHash> = links.into_iter().map(|link| link.target).collect();
+    channel_hashes.sort();
+    let mut expected: Vec<AnyLinkableHash> = vec![open_hash.clone().into(), members_hash.clone().into()];
+    expected.sort();
+    assert_eq!(channel_hashes, expected);
+
+    let links: Vec<Link> = conductors[1]
+        .call(&bob_zome, "get_posts_for_channel", open_hash.clone())
+        .await;
+    assert_eq!(links.len(), 2);
+
+    let links: Vec<Link> = conductors[1]
+        .call(&bob_zome, "get_posts_for_channel", members_hash.clone())
+        .await;
+    assert_eq!(links.len(), 1);
+    assert_eq!(links[0].target, alice_post_hash.clone().into());
+
+    // Bob takes his post out of the open channel
+    let _: () = conductors[1]
+        .call(
+            &bob_zome,
+            "remove_post_from_channel",
+            ChannelPostInput {
+                channel_hash: open_hash.clone(),
+                post_hash: bob_post_hash.clone(),
+            },
+        )
+        .await;
+
+    await_consistency(Duration::from_secs(60), [&alice, &bobbo])
+        .await
+        .expect("Timed out waiting for consistency");
+
+    let links: Vec<Link> = conductors[0]
+        .call(&alice_zome, "get_posts_for_channel", open_hash)
+        .await;
+    assert_eq!(links.len(), 1);
+    assert_eq!(links[0].target, alice_post_hash.into());
+}
@@ -0,0 +1,91 @@
+#![allow(dead_code)]
+#![allow(unused_variables)]
+#![allow(unused_imports)]
+
+use hdk::prelude::*;
+use holochain::sweettest::*;
+use std::time::Duration;
+
+use posts_client::*;
+
+mod common;
+use common::{sample_post_1, sample_post_2, setup};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn typed_client_calls_the_posts_zome() {
+    // Set up conductors
+    let (conductors, alice, bobbo) = setup().await;
+
+    let alice_client = PostsClient::sweettest(&conductors[0], &alice);
+    let bob_client = PostsClient::sweettest(&conductors[1], &bobbo);
+
+    let alice_zome = alice.zome("posts");
+
+    // Alice creates and updates a Post
+    let post = alice_client
+        .create_post(sample_post_1(&conductors[0], &alice_zome).await)
+        .await
+        .unwrap();
+    let post_hash = post.signed_action.hashed.hash.clone();
+    let update = alice_client
+        .update_post(UpdatePostInput {
+            original_post_hash: post_hash.clone(),
+            previous_post_hash: post_hash.clone(),
+            updated_post: sample_post_2(&conductors[0], &alice_zome).await,
+        })
+        .await
+        .unwrap();
+
+    // Bob comments on it and certifies Alice for it
+    let comment = bob_client
+        .create_comment(Comment {
+            post_hash: post_hash.clone(),
+        })
+        .await
+        .unwrap();
+    let certificate = bob_client
+        .create_certificate(Certificate {
+            post_hash: post_hash.clone(),
+            agent: alice.agent_pubkey().clone(),
+            certifications_hashes: vec![],
+            certificate_type: CertificateType::TypeOne,
+            dna_hash: alice.cell_id().dna_hash().clone(),
+        })
+        .await
+        .unwrap();
+
+    await_consistency(Duration::from_secs(60), [&alice, &bobbo])
+        .await
+        .expect("Timed out waiting for consistency");
+
+    for client in [&alice_client, &bob_client] {
+        assert_eq!(client.get_latest_post(post_hash.clone()).await.unwrap(), Some(update.clone()));
+
+        let links = client.get_all_posts().await.unwrap();
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].target, post_hash.clone().into());
+
+        let links = client.get_comments_for_post(post_hash.clone()).await.unwrap();
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].target, comment.signed_action.hashed.hash.clone().into());
+
+        let links = client
+            .get_certificates_for_certified(alice.agent_pubkey().clone())
+            .await
+            .unwrap();
+        assert_eq!(links.len(), 1);
+        assert_eq!(links[0].target, certificate.signed_action.hashed.hash.clone().into());
+    }
+
+    // Failing calls surface as errors
+    let result = alice_client
+        .create_post(Post {
+            title: String::new(),
+            needs: vec![],
+            body: String::new(),
+            format: ContentFormat::Plain,
+            attachments: vec![],
+        })
+        .await;
+    assert!(matches!(result, Err(PostsClientError::Call(_))));
+}
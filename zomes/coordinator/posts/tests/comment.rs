@@ -4,31 +4,21 @@
 
 use std::time::Duration;
 use hdk::prelude::*;
-use holochain::{conductor::config::ConductorConfig, sweettest::*};
+use holochain::sweettest::*;
 
 use posts_integrity::*;
 
 use posts::comment::UpdateCommentInput;
 
 mod common;
-use common::{create_comment, sample_comment_1, sample_comment_2};
+use common::{create_comment, sample_comment_1, sample_comment_2, setup};
 
 use common::{create_post, sample_post_1, sample_post_2};
 
 #[tokio::test(flavor = "multi_thread")]
 async fn create_comment_test() {
-    // Use prebuilt dna file
-    let dna_path = std::env::current_dir()
-        .unwrap()
-        .join(std::env::var("DNA_PATH").expect("DNA_PATH not set, must be run using nix flake check"));
-    let dna = SweetDnaFile::from_bundle(&dna_path).await.unwrap();
-
     // Set up conductors
-    let mut conductors = SweetConductorBatch::from_config(2, ConductorConfig::default()).await;
-    let apps = conductors.setup_app("posts_test", &[dna]).await.unwrap();
-    conductors.exchange_peer_info().await;
-
-    let ((alice,), (_bobbo,)) = apps.into_tuples();
+    let (conductors, alice, _bobbo) = setup().await;
     
     let alice_zome = alice.zome("posts");
     
@@ -43,18 +33,8 @@ async fn create_comment_test() {
 
 #[tokio::test(flavor = "multi_thread")]
 async fn create_and_read_comment() {
-    // Use prebuilt dna file
-    let dna_path = std::env::current_dir()
-        .unwrap()
-        .join(std::env::var("DNA_PATH").expect("DNA_PATH not set, must be run using nix flake check"));
-    let dna = SweetDnaFile::from_bundle(&dna_path).await.unwrap();
-
     // Set up conductors
-    let mut conductors = SweetConductorBatch::from_config(2, ConductorConfig::default()).await;
-    let apps = conductors.setup_app("posts_test", &[dna]).await.unwrap();
-    conductors.exchange_peer_info().await;
-
-    let ((alice,), (bobbo,)) = apps.into_tuples();
+    let (conductors, alice, bobbo) = setup().await;
 
     let alice_zome = alice.zome("posts");
     let bob_zome = bobbo.zome("posts");
@@ -77,18 +57,8 @@ async fn create_and_read_comment() {
 
 #[tokio::test(flavor = "multi_thread")]
 async fn create_and_update_comment() {
-    // Use prebuilt dna file
-    let dna_path = std::env::current_dir()
-        .unwrap()
-        .join(std::env::var("DNA_PATH").expect("DNA_PATH not set, must be run using nix flake check"));
-    let dna = SweetDnaFile::from_bundle(&dna_path).await.unwrap();
-
     // Set up conductors
-    let mut conductors = SweetConductorBatch::from_config(2, ConductorConfig::default()).await;
-    let apps = conductors.setup_app("posts_test", &[dna]).await.unwrap();
-    conductors.exchange_peer_info().await;
-
-    let ((alice,), (bobbo,)) = apps.into_tuples();
+    let (conductors, alice, bobbo) = setup().await;
 
     let alice_zome = alice.zome("posts");
     let bob_zome = bobbo.zome("posts");
@@ -158,18 +128,8 @@ async fn create_and_update_comment() {
 
 #[tokio::test(flavor = "multi_thread")]
 async fn create_and_delete_comment() {
-    // Use prebuilt dna file
-    let dna_path = std::env::current_dir()
-        .unwrap()
-        .join(std::env::var("DNA_PATH").expect("DNA_PATH not set, must be run using nix flake check"));
-    let dna = SweetDnaFile::from_bundle(&dna_path).await.unwrap();
-
     // Set up conductors
-    let mut conductors = SweetConductorBatch::from_config(2, ConductorConfig::default()).await;
-    let apps = conductors.setup_app("posts_test", &[dna]).await.unwrap();
-    conductors.exchange_peer_info().await;
-
-    let ((alice,), (bobbo,)) = apps.into_tuples();
+    let (conductors, alice, bobbo) = setup().await;
     
     let alice_zome = alice.zome("posts");
     let bob_zome = bobbo.zome("posts");
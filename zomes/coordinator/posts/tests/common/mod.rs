@@ -1,4 +1,12 @@
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
 use hdk::prelude::*;
+use holochain::conductor::config::ConductorConfig;
+use holochain::prelude::{
+    CoordinatorZome, DnaFile, DnaModifiersOpt, DnaWasm, IntegrityZome, WasmHash, WasmZome,
+    ZomeDef,
+};
 use holochain::sweettest::*;
 
 use posts_integrity::*;
@@ -9,6 +17,9 @@ pub async fn sample_post_1(conductor: &SweetConductor, zome: &SweetZome) -> Post
     Post {
 	  title: "Lorem ipsum dolor sit amet, consectetur adipiscing elit.".to_string(),
 	  needs: vec!["Lorem ipsum dolor sit amet, consectetur adipiscing elit.".to_string()],
+	  body: String::new(),
+	  format: ContentFormat::Plain,
+	  attachments: vec![],
     }
 }
 
@@ -16,6 +27,9 @@ pub async fn sample_post_2(conductor: &SweetConductor, zome: &SweetZome) -> Post
     Post {
 	  title: "Lorem ipsum 2".to_string(),
 	  needs: vec!["Lorem ipsum 2".to_string()],
+	  body: String::new(),
+	  format: ContentFormat::Plain,
+	  attachments: vec![],
     }
 }
 
@@ -100,3 +114,142 @@ pub async fn create_certificate(conductor: &SweetConductor, zome: &SweetZome, ce
     record
 }
 
+
+pub fn with_properties(dna: DnaFile, properties: PostsProperties) -> DnaFile {
+    dna.update_modifiers(
+        DnaModifiersOpt::none().with_properties(SerializedBytes::try_from(properties).unwrap()),
+    )
+}
+
+fn workspace_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../../..")
+}
+
+// Builds the zomes with cargo once per test binary, into a target dir of their own so that the
+// build does not wait on the lock held by the `cargo test` that is running us
+fn zome_wasm(zome_name: &str) -> DnaWasm {
+    static WASM_DIR: OnceLock<PathBuf> = OnceLock::new();
+    let wasm_dir = WASM_DIR.get_or_init(|| {
+        let target_dir = workspace_dir().join("target").join("sweettest");
+        let status = std::process::Command::new(
+            std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()),
+        )
+        .current_dir(workspace_dir())
+        .args([
+            "build",
+            "--release",
+            "--target",
+            "wasm32-unknown-unknown",
+            "-p",
+            "posts_integrity",
+            "-p",
+            "posts",
+            "-p",
+            "posts_test_utils",
+            "-p",
+            "file_storage_integrity",
+            "-p",
+            "file_storage",
+            "--target-dir",
+        ])
+        .arg(&target_dir)
+        .status()
+        .expect("Failed to run cargo to build the zomes");
+        assert!(status.success(), "Failed to build the zomes");
+
+        target_dir.join("wasm32-unknown-unknown").join("release")
+    });
+    DnaWasm::from(std::fs::read(wasm_dir.join(format!("{}.wasm", zome_name))).unwrap())
+}
+
+// Takes the coordinator zomes of the DNA, each with the integrity zome it depends on. Integrity
+// zomes are added in the order they are first depended on, so `posts_integrity` must come first
+async fn build_dna(zomes: &[(&str, &str)]) -> DnaFile {
+    let mut integrity_zome_names: Vec<&str> = vec![];
+    for (_, integrity_zome_name) in zomes {
+        if !integrity_zome_names.contains(integrity_zome_name) {
+            integrity_zome_names.push(*integrity_zome_name);
+        }
+    }
+    let mut integrity_zomes = vec![];
+    let mut wasms = vec![];
+    for zome_name in integrity_zome_names {
+        let wasm = zome_wasm(zome_name);
+        integrity_zomes.push(IntegrityZome::new(
+            zome_name.to_string().into(),
+            ZomeDef::from_hash(WasmHash::with_data(&wasm).await).into(),
+        ));
+        wasms.push(wasm);
+    }
+    let mut coordinator_zomes = vec![];
+    for (zome_name, integrity_zome_name) in zomes {
+        let wasm = zome_wasm(zome_name);
+        coordinator_zomes.push(CoordinatorZome::new(
+            zome_name.to_string().into(),
+            ZomeDef::Wasm(WasmZome {
+                wasm_hash: WasmHash::with_data(&wasm).await,
+                dependencies: vec![integrity_zome_name.to_string().into()],
+                preserialized_path: None,
+            })
+            .into(),
+        ));
+        wasms.push(wasm);
+    }
+    let (dna, _, _) =
+        SweetDnaFile::unique_from_zomes(integrity_zomes, coordinator_zomes, wasms).await;
+    dna
+}
+
+// Uses the DNA bundle at DNA_PATH when it is set (as `nix flake check` does), otherwise builds
+// a DNA with the posts and file storage zomes out of this workspace
+pub async fn posts_dna() -> DnaFile {
+    if let Ok(dna_path) = std::env::var("DNA_PATH") {
+        let dna_path = std::env::current_dir().unwrap().join(dna_path);
+        return SweetDnaFile::from_bundle(&dna_path).await.unwrap();
+    }
+    build_dna(&[
+        ("posts", "posts_integrity"),
+        ("file_storage", "file_storage_integrity"),
+    ])
+    .await
+}
+
+// Always built out of this workspace, since the bundle at DNA_PATH doesn't ship the
+// `posts_test_utils` zome
+pub async fn posts_dna_with_test_utils() -> DnaFile {
+    build_dna(&[
+        ("posts", "posts_integrity"),
+        ("posts_test_utils", "posts_integrity"),
+        ("file_storage", "file_storage_integrity"),
+    ])
+    .await
+}
+
+pub async fn setup_with_dna(dna: DnaFile) -> (SweetConductorBatch, SweetCell, SweetCell) {
+    let mut conductors = SweetConductorBatch::from_config(2, ConductorConfig::default()).await;
+    let apps = conductors.setup_app("posts_test", &[dna]).await.unwrap();
+    conductors.exchange_peer_info().await;
+
+    let ((alice,), (bobbo,)) = apps.into_tuples();
+    (conductors, alice, bobbo)
+}
+
+pub async fn setup_with_properties(
+    properties: PostsProperties,
+) -> (SweetConductorBatch, SweetCell, SweetCell) {
+    setup_with_dna(with_properties(posts_dna().await, properties)).await
+}
+
+pub async fn setup() -> (SweetConductorBatch, SweetCell, SweetCell) {
+    setup_with_dna(posts_dna().await).await
+}
+
+pub fn assert_rejected<T: std::fmt::Debug, E: std::fmt::Debug>(result: Result<T, E>, reason: &str) {
+    let error = format!("{:?}", result.expect_err("Expected the call to be rejected"));
+    assert!(
+        error.contains(reason),
+        "Expected the call to be rejected with {:?}, but it failed with {}",
+        reason,
+        error
+    );
+}
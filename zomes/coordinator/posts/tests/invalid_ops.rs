@@ -0,0 +1,436 @@
+#![allow(dead_code)]
+#![allow(unused_variables)]
+#![allow(unused_imports)]
+
+use hdk::prelude::*;
+use holochain::sweettest::*;
+use std::time::Duration;
+
+use posts_integrity::*;
+use posts_test_utils::{CreateRawLinkInput, UpdateRawEntryInput};
+
+mod common;
+use common::{
+    assert_rejected, create_certificate, create_like, create_post, posts_dna_with_test_utils,
+    sample_like_1, sample_post_1, sample_post_2, setup_with_dna,
+};
+
+// Invalid ops are rejected by the author's own conductor when the zome call tries to commit
+// them, so they never reach the DHT: the rejection reason surfaces as the error of the call.
+
+// Checks, from another agent's point of view, that the author's activity on the DHT is exactly
+// their own source chain: none of the rejected actions was committed or published
+async fn assert_no_rejected_activity(
+    author_conductor: &SweetConductor,
+    author: &SweetCell,
+    observer_conductor: &SweetConductor,
+    observer: &SweetCell,
+) {
+    await_consistency(Duration::from_secs(60), [author, observer])
+        .await
+        .expect("Timed out waiting for consistency");
+
+    let chain: Vec<Record> = author_conductor
+        .call(&author.zome("posts_test_utils"), "query_my_chain", ())
+        .await;
+    let activity: AgentActivity = observer_conductor
+        .call(
+            &observer.zome("posts_test_utils"),
+            "get_full_agent_activity",
+            author.agent_pubkey().clone(),
+        )
+        .await;
+
+    assert!(activity.rejected_activity.is_empty());
+    let mut chain_hashes: Vec<ActionHash> = chain
+        .iter()
+        .map(|record| record.action_address().clone())
+        .collect();
+    let mut valid_hashes: Vec<ActionHash> = activity
+        .valid_activity
+        .into_iter()
+        .map(|(_seq, action_hash)| action_hash)
+        .collect();
+    chain_hashes.sort();
+    valid_hashes.sort();
+    assert_eq!(valid_hashes, chain_hashes);
+}
+
+async fn create_raw_link(
+    conductor: &SweetConductor,
+    zome: &SweetZome,
+    base_address: impl Into<AnyLinkableHash>,
+    target_address: impl Into<AnyLinkableHash>,
+    link_type: LinkTypes,
+) -> ActionHash {
+    conductor
+        .call(
+            zome,
+            "create_raw_link",
+            CreateRawLinkInput {
+                base_address: base_address.into(),
+                target_address: target_address.into(),
+                link_type,
+                tag: vec![],
+            },
+        )
+        .await
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn invalid_certificate_ops_are_rejected() {
+    // Set up conductors
+    let (conductors, alice, bobbo) = setup_with_dna(posts_dna_with_test_utils().await).await;
+
+    let alice_zome = alice.zome("posts");
+    let alice_raw_zome = alice.zome("posts_test_utils");
+
+    // Alice certifies Bob for one of her Posts
+    let post_record = create_post(&conductors[0], &alice_zome, sample_post_1(&conductors[0], &alice_zome).await).await;
+    let post_hash = post_record.signed_action.hashed.hash.clone();
+    let certificate = Certificate {
+        post_hash: post_hash.clone(),
+        agent: bobbo.agent_pubkey().clone(),
+        certifications_hashes: vec![],
+        certificate_type: CertificateType::TypeOne,
+        dna_hash: alice.cell_id().dna_hash().clone(),
+    };
+    let certificate_record = create_certificate(&conductors[0], &alice_zome, certificate.clone()).await;
+    let certificate_hash = certificate_record.signed_action.hashed.hash.clone();
+    let certificate_entry_hash = certificate_record.action().entry_hash().unwrap().clone();
+
+    // Alice tries to update the Certificate
+    let result: Result<ActionHash, _> = conductors[0]
+        .call_fallible(
+            &alice_raw_zome,
+            "update_raw_entry",
+            UpdateRawEntryInput {
+                original_action_hash: certificate_hash.clone(),
+                entry: EntryTypes::Certificate(Certificate {
+                    certificate_type: CertificateType::TypeTwo,
+                    ..certificate.clone()
+                }),
+            },
+        )
+        .await;
+    assert_rejected(result, "Certificates cannot be updated");
+
+    // Alice tries to delete the Certificate
+    let result: Result<ActionHash, _> = conductors[0]
+        .call_fallible(&alice_raw_zome, "delete_raw_entry", certificate_hash.clone())
+        .await;
+    assert_rejected(result, "Certificates cannot be deleted");
+
+    // Alice tries to delete each of the links to the Certificate
+    let links = [
+        (
+            create_raw_link(&conductors[0], &alice_raw_zome, post_hash.clone(), certificate_hash.clone(), LinkTypes::PostToCertificates).await,
+            "PostToCertificates links cannot be deleted",
+        ),
+        (
+            create_raw_link(&conductors[0], &alice_raw_zome, bobbo.agent_pubkey().clone(), certificate_hash.clone(), LinkTypes::CertifiedToCertificates).await,
+            "CertifiedToCertificates links cannot be deleted",
+        ),
+        (
+            create_raw_link(&conductors[0], &alice_raw_zome, certificate_entry_hash, certificate_hash.clone(), LinkTypes::CertificateToCertificates).await,
+            "CertificateToCertificates links cannot be deleted",
+        ),
+    ];
+    for (create_link_hash, reason) in links {
+        let result: Result<ActionHash, _> = conductors[0]
+            .call_fallible(&alice_raw_zome, "delete_raw_link", create_link_hash)
+            .await;
+        assert_rejected(result, reason);
+    }
+
+    // Alice tries to certify a Post that is not a Post
+    let like_record = create_like(&conductors[0], &alice_zome, sample_like_1(&conductors[0], &alice_zome).await).await;
+    let result: Result<ActionHash, _> = conductors[0]
+        .call_fallible(
+            &alice_raw_zome,
+            "create_raw_entry",
+            EntryTypes::Certificate(Certificate {
+                post_hash: like_record.signed_action.hashed.hash.clone(),
+                ..certificate
+            }),
+        )
+        .await;
+    assert_rejected(result, "Certificates can only be issued for Posts");
+
+    assert_no_rejected_activity(&conductors[0], &alice, &conductors[1], &bobbo).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn invalid_post_and_comment_ops_are_rejected() {
+    // Set up conductors
+    let (conductors, alice, bobbo) = setup_with_dna(posts_dna_with_test_utils().await).await;
+
+    let alice_zome = alice.zome("posts");
+    let alice_raw_zome = alice.zome("posts_test_utils");
+
+    // Alice tries to create a Post without a title
+    let result: Result<ActionHash, _> = conductors[0]
+        .call_fallible(
+            &alice_raw_zome,
+            "create_raw_entry",
+            EntryTypes::Post(Post {
+                title: String::new(),
+                needs: vec![],
+                body: String::new(),
+                format: ContentFormat::Plain,
+                attachments: vec![],
+            }),
+        )
+        .await;
+    assert_rejected(
+        result,
+        "Post title is 0 characters long, but must be between 1 and 200 characters long",
+    );
+
+    // Alice tries to delete the link between a Post and its update
+    let original_record = create_post(&conductors[0], &alice_zome, sample_post_1(&conductors[0], &alice_zome).await).await;
+    let original_hash = original_record.signed_action.hashed.hash.clone();
+    let updated_record = create_post(&conductors[0], &alice_zome, sample_post_2(&conductors[0], &alice_zome).await).await;
+    let updated_hash = updated_record.signed_action.hashed.hash.clone();
+    let create_link_hash = create_raw_link(&conductors[0], &alice_raw_zome, original_hash.clone(), updated_hash, LinkTypes::PostUpdates).await;
+    let result: Result<ActionHash, _> = conductors[0]
+        .call_fallible(&alice_raw_zome, "delete_raw_link", create_link_hash)
+        .await;
+    assert_rejected(result, "PostUpdates links cannot be deleted");
+
+    // Alice tries to comment on a Like
+    let like_record = create_like(&conductors[0], &alice_zome, sample_like_1(&conductors[0], &alice_zome).await).await;
+    let like_hash = like_record.signed_action.hashed.hash.clone();
+    let result: Result<ActionHash, _> = conductors[0]
+        .call_fallible(
+            &alice_raw_zome,
+            "create_raw_entry",
+            EntryTypes::Comment(Comment {
+                post_hash: like_hash.clone(),
+            }),
+        )
+        .await;
+    assert_rejected(result, "Comments can only be made on Posts");
+
+    // Alice tries to link a Like as a comment of a Post
+    let result: Result<ActionHash, _> = conductors[0]
+        .call_fallible(
+            &alice_raw_zome,
+            "create_raw_link",
+            CreateRawLinkInput {
+                base_address: original_hash.into(),
+                target_address: like_hash.into(),
+                link_type: LinkTypes::PostToComments,
+                tag: vec![],
+            },
+        )
+        .await;
+    assert_rejected(result, "PostToComments links must point to a Comment");
+
+    assert_no_rejected_activity(&conductors[0], &alice, &conductors[1], &bobbo).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn invalid_like_ops_are_rejected() {
+    // Set up conductors
+    let (conductors, alice, bobbo) = setup_with_dna(posts_dna_with_test_utils().await).await;
+
+    let alice_zome = alice.zome("posts");
+    let alice_raw_zome = alice.zome("posts_test_utils");
+
+    // Alice tries to update a Like
+    let like_record = create_like(&conductors[0], &alice_zome, sample_like_1(&conductors[0], &alice_zome).await).await;
+    let like_hash = like_record.signed_action.hashed.hash.clone();
+    let result: Result<ActionHash, _> = conductors[0]
+        .call_fallible(
+            &alice_raw_zome,
+            "update_raw_entry",
+            UpdateRawEntryInput {
+                original_action_hash: like_hash.clone(),
+                entry: EntryTypes::Like(Like {
+                    like_hash: None,
+                    agent: bobbo.agent_pubkey().clone(),
+                }),
+            },
+        )
+        .await;
+    assert_rejected(result, "Likes cannot be updated");
+
+    // Alice tries to like a Post as if it was a Like
+    let post_record = create_post(&conductors[0], &alice_zome, sample_post_1(&conductors[0], &alice_zome).await).await;
+    let result: Result<ActionHash, _> = conductors[0]
+        .call_fallible(
+            &alice_raw_zome,
+            "create_raw_entry",
+            EntryTypes::Like(Like {
+                like_hash: Some(post_record.signed_action.hashed.hash.clone()),
+                agent: alice.agent_pubkey().clone(),
+            }),
+        )
+        .await;
+    assert_rejected(result, "Likes can only reference other Likes");
+
+    assert_no_rejected_activity(&conductors[0], &alice, &conductors[1], &bobbo).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn invalid_moderation_and_report_ops_are_rejected() {
+    // Set up conductors
+    let (conductors, alice, bobbo) = setup_with_dna(posts_dna_with_test_utils().await).await;
+
+    let alice_zome = alice.zome("posts");
+    let alice_raw_zome = alice.zome("posts_test_utils");
+
+    let post_record = create_post(&conductors[0], &alice_zome, sample_post_1(&conductors[0], &alice_zome).await).await;
+    let post_hash = post_record.signed_action.hashed.hash.clone();
+    let other_post_record = create_post(&conductors[0], &alice_zome, sample_post_2(&conductors[0], &alice_zome).await).await;
+    let other_post_hash = other_post_record.signed_action.hashed.hash.clone();
+
+    // Alice, who is not a moderator, tries to hide a Post
+    let result: Result<ActionHash, _> = conductors[0]
+        .call_fallible(
+            &alice_raw_zome,
+            "create_raw_entry",
+            EntryTypes::ModerationAction(ModerationAction {
+                target_hash: post_hash.clone(),
+                decision: ModerationDecision::Hide,
+                reason: "Spam".to_string(),
+                moderator_certificate_hash: None,
+            }),
+        )
+        .await;
+    assert_rejected(result, "Only moderators can create ModerationActions");
+
+    // Alice tries to report a Like
+    let like_record = create_like(&conductors[0], &alice_zome, sample_like_1(&conductors[0], &alice_zome).await).await;
+    let result: Result<ActionHash, _> = conductors[0]
+        .call_fallible(
+            &alice_raw_zome,
+            "create_raw_entry",
+            EntryTypes::Report(Report {
+                target_hash: like_record.signed_action.hashed.hash.clone(),
+                reason: ReportReason::Spam,
+            }),
+        )
+        .await;
+    assert_rejected(result, "Only posts and comments can be reported");
+
+    // Alice reports a Post twice, bypassing the check of create_report, and both of her Reports
+    // count as one
+    let report = Report {
+        target_hash: post_hash.clone(),
+        reason: ReportReason::Spam,
+    };
+    let report_hash: ActionHash = conductors[0]
+        .call(&alice_raw_zome, "create_raw_entry", EntryTypes::Report(report.clone()))
+        .await;
+    let duplicate_report_hash: ActionHash = conductors[0]
+        .call(
+            &alice_raw_zome,
+            "create_raw_entry",
+            EntryTypes::Report(Report {
+                reason: ReportReason::Harassment,
+                ..report
+            }),
+        )
+        .await;
+    for hash in [&report_hash, &duplicate_report_hash] {
+        let _: ActionHash = conductors[0]
+            .call(
+                &alice_raw_zome,
+                "create_raw_link",
+                CreateRawLinkInput {
+                    base_address: post_hash.clone().into(),
+                    target_address: hash.clone().into(),
+                    link_type: LinkTypes::PostToReports,
+                    tag: vec![],
+                },
+            )
+            .await;
+    }
+    let links: Vec<Link> = conductors[0]
+        .call(&alice_zome, "get_reports_for", post_hash.clone())
+        .await;
+    assert_eq!(links.len(), 1);
+    assert_eq!(links[0].target, report_hash.clone().into());
+
+    // Alice tries to update and delete her Report
+    let result: Result<ActionHash, _> = conductors[0]
+        .call_fallible(
+            &alice_raw_zome,
+            "update_raw_entry",
+            UpdateRawEntryInput {
+                original_action_hash: report_hash.clone(),
+                entry: EntryTypes::Report(Report {
+                    target_hash: other_post_hash.clone(),
+                    reason: ReportReason::Harassment,
+                }),
+            },
+        )
+        .await;
+    assert_rejected(result, "Reports cannot be updated");
+    let result: Result<ActionHash, _> = conductors[0]
+        .call_fallible(&alice_raw_zome, "delete_raw_entry", report_hash.clone())
+        .await;
+    assert_rejected(result, "Reports cannot be deleted");
+
+    // Alice tries to link her Report from a Post it does not report, and to delete its link
+    let result: Result<ActionHash, _> = conductors[0]
+        .call_fallible(
+            &alice_raw_zome,
+            "create_raw_link",
+            CreateRawLinkInput {
+                base_address: other_post_hash.into(),
+                target_address: report_hash.clone().into(),
+                link_type: LinkTypes::PostToReports,
+                tag: vec![],
+            },
+        )
+        .await;
+    assert_rejected(result, "Reports must be linked from the item they report");
+    let create_link_hash = create_raw_link(&conductors[0], &alice_raw_zome, post_hash, report_hash, LinkTypes::PostToReports).await;
+    let result: Result<ActionHash, _> = conductors[0]
+        .call_fallible(&alice_raw_zome, "delete_raw_link", create_link_hash)
+        .await;
+    assert_rejected(result, "PostToReports links cannot be deleted");
+
+    assert_no_rejected_activity(&conductors[0], &alice, &conductors[1], &bobbo).await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn linking_a_post_to_an_agents_likes_is_rejected() {
+    // Set up conductors
+    let (conductors, alice, bobbo) = setup_with_dna(posts_dna_with_test_utils().await).await;
+
+    let alice_raw_zome = alice.zome("posts_test_utils");
+
+    // Alice tries to pass one of her Posts off as a Like of Bob
+    let post_hash: ActionHash = conductors[0]
+        .call(
+            &alice_raw_zome,
+            "create_raw_entry",
+            EntryTypes::Post(Post {
+                title: "Not a like".to_string(),
+                needs: vec![],
+                body: String::new(),
+                format: ContentFormat::Plain,
+                attachments: vec![],
+            }),
+        )
+        .await;
+    let result: Result<ActionHash, _> = conductors[0]
+        .call_fallible(
+            &alice_raw_zome,
+            "create_raw_link",
+            CreateRawLinkInput {
+                base_address: bobbo.agent_pubkey().clone().into(),
+                target_address: post_hash.into(),
+                link_type: LinkTypes::AgentToLikes,
+                tag: vec![],
+            },
+        )
+        .await;
+    assert_rejected(result, "AgentToLikes links must point to a Like");
+
+    assert_no_rejected_activity(&conductors[0], &alice, &conductors[1], &bobbo).await;
+}
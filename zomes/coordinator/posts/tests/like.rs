@@ -4,29 +4,19 @@
 
 use std::time::Duration;
 use hdk::prelude::*;
-use holochain::{conductor::config::ConductorConfig, sweettest::*};
+use holochain::sweettest::*;
 
 use posts_integrity::*;
 
 
 mod common;
-use common::{create_like, sample_like_1, sample_like_2};
+use common::{create_like, sample_like_1, sample_like_2, setup};
 
 
 #[tokio::test(flavor = "multi_thread")]
 async fn create_like_test() {
-    // Use prebuilt dna file
-    let dna_path = std::env::current_dir()
-        .unwrap()
-        .join(std::env::var("DNA_PATH").expect("DNA_PATH not set, must be run using nix flake check"));
-    let dna = SweetDnaFile::from_bundle(&dna_path).await.unwrap();
-
     // Set up conductors
-    let mut conductors = SweetConductorBatch::from_config(2, ConductorConfig::default()).await;
-    let apps = conductors.setup_app("posts_test", &[dna]).await.unwrap();
-    conductors.exchange_peer_info().await;
-
-    let ((alice,), (_bobbo,)) = apps.into_tuples();
+    let (conductors, alice, _bobbo) = setup().await;
     
     let alice_zome = alice.zome("posts");
     
@@ -41,18 +31,8 @@ async fn create_like_test() {
 
 #[tokio::test(flavor = "multi_thread")]
 async fn create_and_read_like() {
-    // Use prebuilt dna file
-    let dna_path = std::env::current_dir()
-        .unwrap()
-        .join(std::env::var("DNA_PATH").expect("DNA_PATH not set, must be run using nix flake check"));
-    let dna = SweetDnaFile::from_bundle(&dna_path).await.unwrap();
-
     // Set up conductors
-    let mut conductors = SweetConductorBatch::from_config(2, ConductorConfig::default()).await;
-    let apps = conductors.setup_app("posts_test", &[dna]).await.unwrap();
-    conductors.exchange_peer_info().await;
-
-    let ((alice,), (bobbo,)) = apps.into_tuples();
+    let (conductors, alice, bobbo) = setup().await;
 
     let alice_zome = alice.zome("posts");
     let bob_zome = bobbo.zome("posts");
@@ -76,18 +56,8 @@ async fn create_and_read_like() {
 
 #[tokio::test(flavor = "multi_thread")]
 async fn create_and_delete_like() {
-    // Use prebuilt dna file
-    let dna_path = std::env::current_dir()
-        .unwrap()
-        .join(std::env::var("DNA_PATH").expect("DNA_PATH not set, must be run using nix flake check"));
-    let dna = SweetDnaFile::from_bundle(&dna_path).await.unwrap();
-
     // Set up conductors
-    let mut conductors = SweetConductorBatch::from_config(2, ConductorConfig::default()).await;
-    let apps = conductors.setup_app("posts_test", &[dna]).await.unwrap();
-    conductors.exchange_peer_info().await;
-
-    let ((alice,), (bobbo,)) = apps.into_tuples();
+    let (conductors, alice, bobbo) = setup().await;
     
     let alice_zome = alice.zome("posts");
     let bob_zome = bobbo.zome("posts");
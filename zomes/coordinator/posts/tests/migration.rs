@@ -0,0 +1,129 @@
+#![allow(dead_code)]
+#![allow(unused_variables)]
+#![allow(unused_imports)]
+
+use hdk::prelude::*;
+use holochain::conductor::config::ConductorConfig;
+use holochain::sweettest::*;
+use std::time::Duration;
+
+use posts_integrity::*;
+
+use posts::post::UpdatePostInput;
+
+mod common;
+use common::{create_post, posts_dna, sample_post_1, sample_post_2, with_properties};
+
+// Both DNA versions are installed side by side in one app, so that every agent has a cell of
+// each with the same key, and the new version can call into the old one
+#[tokio::test(flavor = "multi_thread")]
+async fn posts_of_the_previous_dna_are_copied_forward() {
+    let previous_dna = posts_dna().await;
+    let dna = with_properties(
+        posts_dna().await,
+        PostsProperties {
+            previous_dna_hash: Some(previous_dna.dna_hash().clone()),
+            ..Default::default()
+        },
+    );
+
+    let mut conductors = SweetConductorBatch::from_config(2, ConductorConfig::default()).await;
+    let apps = conductors
+        .setup_app("posts_test", &[previous_dna.clone(), dna.clone()])
+        .await
+        .unwrap();
+    conductors.exchange_peer_info().await;
+    let ((alice_previous, alice), (bob_previous, bobbo)) = apps.into_tuples();
+
+    let alice_previous_zome = alice_previous.zome("posts");
+    let alice_zome = alice.zome("posts");
+    let bob_zome = bobbo.zome("posts");
+    let bob_previous_zome = bob_previous.zome("posts");
+
+    // Alice creates and updates a Post in the previous DNA
+    let previous_post = create_post(
+        &conductors[0],
+        &alice_previous_zome,
+        sample_post_1(&conductors[0], &alice_previous_zome).await,
+    )
+    .await;
+    let previous_post_hash = previous_post.signed_action.hashed.hash.clone();
+    let _: Record = conductors[0]
+        .call(
+            &alice_previous_zome,
+            "update_post",
+            UpdatePostInput {
+                original_post_hash: previous_post_hash.clone(),
+                previous_post_hash: previous_post_hash.clone(),
+                updated_post: sample_post_2(&conductors[0], &alice_previous_zome).await,
+            },
+        )
+        .await;
+
+    await_consistency(Duration::from_secs(60), [&alice_previous, &bob_previous])
+        .await
+        .expect("Timed out waiting for consistency in the previous DNA");
+
+    // The previous DNA has no predecessor of its own
+    let migration_pointer: Option<MigrationPointer> = conductors[1]
+        .call(&bob_previous_zome, "record_migration_pointer", ())
+        .await;
+    assert!(migration_pointer.is_none());
+
+    // Bob records where the new DNA comes from
+    let migration_pointer: Option<MigrationPointer> = conductors[1]
+        .call(&bob_zome, "record_migration_pointer", ())
+        .await;
+    assert_eq!(
+        migration_pointer,
+        Some(MigrationPointer {
+            previous_dna_hash: previous_dna.dna_hash().clone(),
+        })
+    );
+
+    // Bob reads the previous DNA's posts, but only Alice can copy hers forward
+    let links: Vec<Link> = conductors[1].call(&bob_zome, "get_previous_posts", ()).await;
+    assert_eq!(links.len(), 1);
+    assert_eq!(links[0].target, previous_post_hash.clone().into());
+
+    let migrated: Vec<Record> = conductors[1].call(&bob_zome, "migrate_my_posts", ()).await;
+    assert!(migrated.is_empty());
+
+    await_consistency(Duration::from_secs(60), [&alice, &bobbo])
+        .await
+        .expect("Timed out waiting for consistency in the new DNA");
+
+    let migration_pointer: Option<MigrationPointer> = conductors[0]
+        .call(&alice_zome, "get_migration_pointer", ())
+        .await;
+    assert!(migration_pointer.is_some());
+
+    let migrated: Vec<Record> = conductors[0].call(&alice_zome, "migrate_my_posts", ()).await;
+    assert_eq!(migrated.len(), 1);
+    let migrated = migrated[0].clone();
+    assert_eq!(migrated.action().author(), alice.agent_pubkey());
+    let migrated_post: Post = migrated.entry().to_app_option().unwrap().unwrap();
+    assert_eq!(migrated_post, sample_post_2(&conductors[0], &alice_zome).await);
+
+    await_consistency(Duration::from_secs(60), [&alice, &bobbo])
+        .await
+        .expect("Timed out waiting for consistency in the new DNA");
+
+    // Bob finds Alice's copy, and migrating again doesn't make another one
+    let migrated_for_bob: Option<Record> = conductors[1]
+        .call(&bob_zome, "get_migrated_post", previous_post_hash.clone())
+        .await;
+    assert_eq!(migrated_for_bob, Some(migrated.clone()));
+
+    let migrated_again: Vec<Record> = conductors[0].call(&alice_zome, "migrate_my_posts", ()).await;
+    assert!(migrated_again.is_empty());
+
+    let links: Vec<Link> = conductors[0].call(&alice_zome, "get_all_posts", ()).await;
+    assert_eq!(links.len(), 1);
+    assert_eq!(links[0].target, migrated.signed_action.hashed.hash.clone().into());
+
+    // The previous DNA is left untouched
+    let links: Vec<Link> = conductors[0].call(&alice_previous_zome, "get_all_posts", ()).await;
+    assert_eq!(links.len(), 1);
+    assert_eq!(links[0].target, previous_post_hash.into());
+}
@@ -0,0 +1,159 @@
+#![allow(dead_code)]
+#![allow(unused_variables)]
+#![allow(unused_imports)]
+
+use hdk::prelude::*;
+use holochain::{conductor::config::ConductorConfig, sweettest::*};
+use std::time::Duration;
+
+use posts_integrity::*;
+
+use posts::comment::GetCommentsForPostInput;
+use posts::moderation_action::ListOptions;
+
+mod common;
+use common::{create_comment, create_post, posts_dna, sample_post_1, sample_post_2, with_properties};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn moderator_can_hide_posts_and_comments() {
+    let dna = posts_dna().await;
+
+    // Set up conductors, with Alice as the moderator
+    let mut conductors = SweetConductorBatch::from_config(2, ConductorConfig::default()).await;
+    let alice_pubkey = SweetAgents::one(conductors[0].keystore()).await;
+    let dna = with_properties(
+        dna,
+        PostsProperties {
+            moderators: vec![alice_pubkey.clone()],
+            ..Default::default()
+        },
+    );
+    let alice_app = conductors[0]
+        .setup_app_for_agent("posts_test", alice_pubkey.clone(), &[dna.clone()])
+        .await
+        .unwrap();
+    let bob_app = conductors[1].setup_app("posts_test", &[dna]).await.unwrap();
+    conductors.exchange_peer_info().await;
+
+    let (alice,) = alice_app.into_tuple();
+    let (bobbo,) = bob_app.into_tuple();
+
+    let alice_zome = alice.zome("posts");
+    let bob_zome = bobbo.zome("posts");
+
+    // Bob creates two Posts and a Comment
+    let abusive_post = create_post(&conductors[1], &bob_zome, sample_post_1(&conductors[1], &bob_zome).await).await;
+    let abusive_post_hash = abusive_post.signed_action.hashed.hash.clone();
+    create_post(&conductors[1], &bob_zome, sample_post_2(&conductors[1], &bob_zome).await).await;
+    let abusive_comment = create_comment(
+        &conductors[1],
+        &bob_zome,
+        Comment {
+            post_hash: abusive_post_hash.clone(),
+        },
+    )
+    .await;
+
+    await_consistency(Duration::from_secs(60), [&alice, &bobbo])
+        .await
+        .expect("Timed out waiting for consistency");
+
+    // Bob is not a moderator
+    let result: Result<Record, _> = conductors[1]
+        .call_fallible(
+            &bob_zome,
+            "create_moderation_action",
+            ModerationAction {
+                target_hash: abusive_post_hash.clone(),
+                decision: ModerationDecision::Hide,
+                reason: "Spam".to_string(),
+                moderator_certificate_hash: None,
+            },
+        )
+        .await;
+    assert!(result.is_err());
+
+    // Alice hides the Post and the Comment
+    for target_hash in [
+        abusive_post_hash.clone(),
+        abusive_comment.signed_action.hashed.hash.clone(),
+    ] {
+        let _: Record = conductors[0]
+            .call(
+                &alice_zome,
+                "create_moderation_action",
+                ModerationAction {
+                    target_hash,
+                    decision: ModerationDecision::Hide,
+                    reason: "Spam".to_string(),
+                    moderator_certificate_hash: None,
+                },
+            )
+            .await;
+    }
+
+    await_consistency(Duration::from_secs(60), [&alice, &bobbo])
+        .await
+        .expect("Timed out waiting for consistency");
+
+    let links: Vec<Link> = conductors[1].call(&bob_zome, "get_all_posts", ()).await;
+    assert_eq!(links.len(), 2);
+
+    let links: Vec<Link> = conductors[1]
+        .call(
+            &bob_zome,
+            "get_all_posts_with_options",
+            ListOptions {
+                exclude_moderated: true,
+            },
+        )
+        .await;
+    assert_eq!(links.len(), 1);
+    assert_ne!(
+        links[0].target.clone().into_action_hash().unwrap(),
+        abusive_post_hash
+    );
+
+    let links: Vec<Link> = conductors[1]
+        .call(
+            &bob_zome,
+            "get_comments_for_post_with_options",
+            GetCommentsForPostInput {
+                post_hash: abusive_post_hash.clone(),
+                options: ListOptions {
+                    exclude_moderated: true,
+                },
+            },
+        )
+        .await;
+    assert_eq!(links.len(), 0);
+
+    // Alice reverts her decision on the Post
+    let _: Record = conductors[0]
+        .call(
+            &alice_zome,
+            "create_moderation_action",
+            ModerationAction {
+                target_hash: abusive_post_hash.clone(),
+                decision: ModerationDecision::Unhide,
+                reason: "Appeal accepted".to_string(),
+                moderator_certificate_hash: None,
+            },
+        )
+        .await;
+
+    await_consistency(Duration::from_secs(60), [&alice, &bobbo])
+        .await
+        .expect("Timed out waiting for consistency");
+
+    let links: Vec<Link> = conductors[1]
+        .call(
+            &bob_zome,
+            "get_all_posts_with_options",
+            ListOptions {
+                exclude_moderated: true,
+            },
+        )
+        .await;
+    assert_eq!(links.len(), 2);
+}
@@ -0,0 +1,272 @@
+#![allow(dead_code)]
+#![allow(unused_variables)]
+#![allow(unused_imports)]
+
+use hdk::prelude::*;
+use holochain::sweettest::*;
+use std::time::Duration;
+
+use posts::notification::NotificationWithStatus;
+use posts::pagination::Page;
+use posts_integrity::*;
+
+mod common;
+use common::{create_comment, create_post, sample_post_1, setup};
+
+async fn next_notification(
+    signals: &mut tokio::sync::broadcast::Receiver<holochain::prelude::Signal>,
+) -> (AgentPubKey, NotificationEvent) {
+    tokio::time::timeout(Duration::from_secs(30), async {
+        loop {
+            let Ok(holochain::prelude::Signal::App { signal, .. }) = signals.recv().await else {
+                continue;
+            };
+            if let Ok(posts::Signal::Notification { from, event, .. }) =
+                signal.into_inner().decode::<posts::Signal>()
+            {
+                return (from, event);
+            }
+        }
+    })
+    .await
+    .expect("Timed out waiting for a notification")
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn post_author_is_notified_of_new_comment() {
+    // Set up conductors
+    let (conductors, alice, bobbo) = setup().await;
+
+    let alice_zome = alice.zome("posts");
+    let bob_zome = bobbo.zome("posts");
+
+    let mut alice_signals = conductors[0].subscribe_to_app_signals("posts_test".into());
+
+    // Alice creates a Post
+    let post_record = create_post(&conductors[0], &alice_zome, sample_post_1(&conductors[0], &alice_zome).await).await;
+    let post_hash = post_record.signed_action.hashed.hash.clone();
+
+    await_consistency(Duration::from_secs(60), [&alice, &bobbo])
+        .await
+        .expect("Timed out waiting for consistency");
+
+    // Bob comments on Alice's Post
+    let comment_record = create_comment(
+        &conductors[1],
+        &bob_zome,
+        Comment {
+            post_hash: post_hash.clone(),
+        },
+    )
+    .await;
+
+    let (from, event) = next_notification(&mut alice_signals).await;
+
+    assert_eq!(from, bobbo.agent_pubkey().clone());
+    assert_eq!(
+        event,
+        NotificationEvent::CommentCreated {
+            post_hash,
+            comment_hash: comment_record.signed_action.hashed.hash,
+        }
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn certified_agent_is_notified_of_new_certificate() {
+    // Set up conductors
+    let (conductors, alice, bobbo) = setup().await;
+
+    let alice_zome = alice.zome("posts");
+
+    let mut bob_signals = conductors[1].subscribe_to_app_signals("posts_test".into());
+
+    // Alice creates a Post and certifies Bob for it
+    let post_record = create_post(&conductors[0], &alice_zome, sample_post_1(&conductors[0], &alice_zome).await).await;
+    let post_hash = post_record.signed_action.hashed.hash.clone();
+    let certificate = Certificate {
+        post_hash: post_hash.clone(),
+        agent: bobbo.agent_pubkey().clone(),
+        certifications_hashes: vec![],
+        certificate_type: CertificateType::TypeOne,
+        dna_hash: alice.cell_id().dna_hash().clone(),
+    };
+    let certificate_record: Record = conductors[0]
+        .call(&alice_zome, "create_certificate", certificate)
+        .await;
+
+    let (from, event) = next_notification(&mut bob_signals).await;
+
+    assert_eq!(from, alice.agent_pubkey().clone());
+    assert_eq!(
+        event,
+        NotificationEvent::CertificateCreated {
+            post_hash,
+            certificate_hash: certificate_record.signed_action.hashed.hash,
+        }
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn notifications_are_stored_until_marked_read() {
+    // Set up conductors
+    let (conductors, alice, bobbo) = setup().await;
+
+    let alice_zome = alice.zome("posts");
+    let bob_zome = bobbo.zome("posts");
+
+    let mut alice_signals = conductors[0].subscribe_to_app_signals("posts_test".into());
+
+    // Alice creates a Post
+    let post_record = create_post(&conductors[0], &alice_zome, sample_post_1(&conductors[0], &alice_zome).await).await;
+    let post_hash = post_record.signed_action.hashed.hash.clone();
+
+    await_consistency(Duration::from_secs(60), [&alice, &bobbo])
+        .await
+        .expect("Timed out waiting for consistency");
+
+    // Bob comments on Alice's Post
+    create_comment(&conductors[1], &bob_zome, Comment { post_hash }).await;
+    let (_from, event) = next_notification(&mut alice_signals).await;
+
+    let unread_count: usize = conductors[0]
+        .call(&alice_zome, "get_unread_count", ())
+        .await;
+    assert_eq!(unread_count, 1);
+
+    let notifications: Vec<NotificationWithStatus> = conductors[0]
+        .call(&alice_zome, "get_notifications", Page { page: 0, page_size: 10 })
+        .await;
+    assert_eq!(notifications.len(), 1);
+    assert_eq!(notifications[0].notification.event, event);
+    assert_eq!(notifications[0].notification.from, bobbo.agent_pubkey().clone());
+    assert!(!notifications[0].read);
+
+    // Alice marks the notification as read
+    let _: () = conductors[0]
+        .call(
+            &alice_zome,
+            "mark_notifications_read",
+            vec![notifications[0].notification_hash.clone()],
+        )
+        .await;
+
+    let unread_count: usize = conductors[0]
+        .call(&alice_zome, "get_unread_count", ())
+        .await;
+    assert_eq!(unread_count, 0);
+
+    let notifications: Vec<NotificationWithStatus> = conductors[0]
+        .call(&alice_zome, "get_notifications", Page { page: 0, page_size: 10 })
+        .await;
+    assert_eq!(notifications.len(), 1);
+    assert!(notifications[0].read);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn notifications_are_received_after_being_offline() {
+    // Set up conductors
+    let (mut conductors, alice, bobbo) = setup().await;
+
+    let alice_zome = alice.zome("posts");
+    let bob_zome = bobbo.zome("posts");
+
+    // Alice creates a Post and goes offline
+    let post_record = create_post(&conductors[0], &alice_zome, sample_post_1(&conductors[0], &alice_zome).await).await;
+    let post_hash = post_record.signed_action.hashed.hash.clone();
+
+    await_consistency(Duration::from_secs(60), [&alice, &bobbo])
+        .await
+        .expect("Timed out waiting for consistency");
+
+    conductors[0].shutdown().await;
+
+    // Bob comments on Alice's Post while she can't receive any signal
+    let comment_record = create_comment(
+        &conductors[1],
+        &bob_zome,
+        Comment {
+            post_hash: post_hash.clone(),
+        },
+    )
+    .await;
+
+    conductors[0].startup().await;
+    conductors.exchange_peer_info().await;
+
+    await_consistency(Duration::from_secs(60), [&alice, &bobbo])
+        .await
+        .expect("Timed out waiting for consistency");
+
+    let notifications: Vec<NotificationWithStatus> = conductors[0]
+        .call(&alice_zome, "get_notifications", Page { page: 0, page_size: 10 })
+        .await;
+    assert_eq!(notifications.len(), 1);
+    assert_eq!(notifications[0].notification.from, bobbo.agent_pubkey().clone());
+    assert_eq!(
+        notifications[0].notification.event,
+        NotificationEvent::CommentCreated {
+            post_hash,
+            comment_hash: comment_record.signed_action.hashed.hash,
+        }
+    );
+    assert!(!notifications[0].read);
+
+    let unread_count: usize = conductors[0]
+        .call(&alice_zome, "get_unread_count", ())
+        .await;
+    assert_eq!(unread_count, 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn muted_agents_still_cause_notifications_but_blocked_agents_do_not() {
+    // Set up conductors
+    let (conductors, alice, bobbo) = setup().await;
+
+    let alice_zome = alice.zome("posts");
+    let bob_zome = bobbo.zome("posts");
+
+    let mut alice_signals = conductors[0].subscribe_to_app_signals("posts_test".into());
+
+    // Alice creates a Post and mutes Bob
+    let post_record = create_post(&conductors[0], &alice_zome, sample_post_1(&conductors[0], &alice_zome).await).await;
+    let post_hash = post_record.signed_action.hashed.hash.clone();
+    let _: BlockList = conductors[0]
+        .call(&alice_zome, "mute_agent", bobbo.agent_pubkey().clone())
+        .await;
+
+    await_consistency(Duration::from_secs(60), [&alice, &bobbo])
+        .await
+        .expect("Timed out waiting for consistency");
+
+    // Bob's Comment still reaches Alice
+    create_comment(
+        &conductors[1],
+        &bob_zome,
+        Comment {
+            post_hash: post_hash.clone(),
+        },
+    )
+    .await;
+
+    let (from, _event) = next_notification(&mut alice_signals).await;
+    assert_eq!(from, bobbo.agent_pubkey().clone());
+
+    await_consistency(Duration::from_secs(60), [&alice, &bobbo])
+        .await
+        .expect("Timed out waiting for consistency");
+
+    let notifications: Vec<NotificationWithStatus> = conductors[0]
+        .call(&alice_zome, "get_notifications", Page { page: 0, page_size: 10 })
+        .await;
+    assert_eq!(notifications.len(), 1);
+
+    // Once Alice blocks Bob, his Comment is no longer in her inbox
+    let _: BlockList = conductors[0]
+        .call(&alice_zome, "block_agent", bobbo.agent_pubkey().clone())
+        .await;
+    let notifications: Vec<NotificationWithStatus> = conductors[0]
+        .call(&alice_zome, "get_notifications", Page { page: 0, page_size: 10 })
+        .await;
+    assert!(notifications.is_empty());
+}
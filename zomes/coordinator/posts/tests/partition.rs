@@ -0,0 +1,271 @@
+#![allow(dead_code)]
+#![allow(unused_variables)]
+#![allow(unused_imports)]
+
+use hdk::prelude::*;
+use holochain::conductor::config::ConductorConfig;
+use holochain::sweettest::*;
+use std::time::Duration;
+
+use posts_integrity::*;
+
+use posts::post::UpdatePostInput;
+
+mod common;
+use common::{create_certificate, create_comment, create_post, posts_dna, sample_post_1, sample_post_2};
+
+fn certificate(post_hash: &ActionHash, agent: &SweetCell, certifications_hashes: Vec<EntryHash>) -> Certificate {
+    Certificate {
+        post_hash: post_hash.clone(),
+        agent: agent.agent_pubkey().clone(),
+        certifications_hashes,
+        certificate_type: CertificateType::TypeOne,
+        dna_hash: agent.cell_id().dna_hash().clone(),
+    }
+}
+
+fn entry_hash(record: &Record) -> EntryHash {
+    record.action().entry_hash().unwrap().clone()
+}
+
+fn action_hash(record: &Record) -> ActionHash {
+    record.signed_action.hashed.hash.clone()
+}
+
+fn sorted_targets(links: Vec<Link>) -> Vec<AnyLinkableHash> {
+    let mut targets: Vec<AnyLinkableHash> = links.into_iter().map(|link| link.target).collect();
+    targets.sort();
+    targets
+}
+
+// Carol's conductor is shut down while Alice and Bob keep creating content, and has to
+// catch up with all of it once it is started again
+#[tokio::test(flavor = "multi_thread")]
+async fn offline_conductor_converges_after_rejoining() {
+    // Set up conductors
+    let mut conductors = SweetConductorBatch::from_config(3, ConductorConfig::default()).await;
+    let apps = conductors.setup_app("posts_test", &[posts_dna().await]).await.unwrap();
+    conductors.exchange_peer_info().await;
+
+    let ((alice,), (bobbo,), (carol,)) = apps.into_tuples();
+
+    let alice_zome = alice.zome("posts");
+    let bob_zome = bobbo.zome("posts");
+    let carol_zome = carol.zome("posts");
+
+    // Carol creates a Post that everyone has seen before she goes offline
+    let carol_post = create_post(&conductors[2], &carol_zome, sample_post_1(&conductors[2], &carol_zome).await).await;
+
+    await_consistency(Duration::from_secs(60), [&alice, &bobbo, &carol])
+        .await
+        .expect("Timed out waiting for consistency before the partition");
+
+    conductors[2].shutdown().await;
+
+    // Alice creates and updates a Post, Bob creates another one
+    let alice_post = create_post(&conductors[0], &alice_zome, sample_post_1(&conductors[0], &alice_zome).await).await;
+    let first_update: Record = conductors[0]
+        .call(
+            &alice_zome,
+            "update_post",
+            UpdatePostInput {
+                original_post_hash: action_hash(&alice_post),
+                previous_post_hash: action_hash(&alice_post),
+                updated_post: sample_post_2(&conductors[0], &alice_zome).await,
+            },
+        )
+        .await;
+    let latest_update: Record = conductors[0]
+        .call(
+            &alice_zome,
+            "update_post",
+            UpdatePostInput {
+                original_post_hash: action_hash(&alice_post),
+                previous_post_hash: action_hash(&first_update),
+                updated_post: Post {
+                    title: "Updated while Carol was offline".to_string(),
+                    needs: vec![],
+                    body: String::new(),
+                    format: ContentFormat::Plain,
+                    attachments: vec![],
+                },
+            },
+        )
+        .await;
+    let bob_post = create_post(&conductors[1], &bob_zome, sample_post_2(&conductors[1], &bob_zome).await).await;
+
+    // Alice and Bob certify Carol for her Post, in a chain that spans both of them
+    let certificate_a = create_certificate(
+        &conductors[0],
+        &alice_zome,
+        certificate(&action_hash(&carol_post), &carol, vec![]),
+    )
+    .await;
+
+    await_consistency(Duration::from_secs(60), [&alice, &bobbo])
+        .await
+        .expect("Timed out waiting for consistency during the partition");
+
+    let certificate_b = create_certificate(
+        &conductors[1],
+        &bob_zome,
+        certificate(&action_hash(&carol_post), &carol, vec![entry_hash(&certificate_a)]),
+    )
+    .await;
+
+    await_consistency(Duration::from_secs(60), [&alice, &bobbo])
+        .await
+        .expect("Timed out waiting for consistency during the partition");
+
+    // Carol comes back online
+    conductors[2].startup().await;
+    conductors.exchange_peer_info().await;
+
+    await_consistency(Duration::from_secs(120), [&alice, &bobbo, &carol])
+        .await
+        .expect("Timed out waiting for consistency after the partition healed");
+
+    // All three conductors agree on the state of the DHT
+    let mut expected_posts = vec![
+        AnyLinkableHash::from(action_hash(&carol_post)),
+        AnyLinkableHash::from(action_hash(&alice_post)),
+        AnyLinkableHash::from(action_hash(&bob_post)),
+    ];
+    expected_posts.sort();
+    let mut expected_certificates = vec![
+        AnyLinkableHash::from(action_hash(&certificate_a)),
+        AnyLinkableHash::from(action_hash(&certificate_b)),
+    ];
+    expected_certificates.sort();
+
+    for (conductor, zome) in [
+        (&conductors[0], &alice_zome),
+        (&conductors[1], &bob_zome),
+        (&conductors[2], &carol_zome),
+    ] {
+        let latest: Option<Record> = conductor
+            .call(zome, "get_latest_post", action_hash(&alice_post))
+            .await;
+        assert_eq!(latest.unwrap(), latest_update);
+
+        let links: Vec<Link> = conductor
+            .call(zome, "get_certificates_for_certified", carol.agent_pubkey().clone())
+            .await;
+        assert_eq!(sorted_targets(links), expected_certificates);
+
+        let links: Vec<Link> = conductor.call(zome, "get_all_posts", ()).await;
+        assert_eq!(sorted_targets(links), expected_posts);
+    }
+}
+
+// A brand-new agent joins once hundreds of items exist, and has to fetch the dependencies
+// of all of them to validate what it receives and what it authors on top of them
+#[tokio::test(flavor = "multi_thread")]
+async fn late_joiner_catches_up_with_existing_content() {
+    let post_count: usize = std::env::var("POSTS_LATE_JOINER_POSTS")
+        .ok()
+        .map(|count| count.parse().unwrap())
+        .unwrap_or(100);
+
+    // Set up conductors, leaving the third one without any app for now
+    let mut conductors = SweetConductorBatch::from_config(3, ConductorConfig::default()).await;
+    let dna = posts_dna().await;
+    let (alice,) = conductors[0].setup_app("posts_test", &[dna.clone()]).await.unwrap().into_tuple();
+    let (bobbo,) = conductors[1].setup_app("posts_test", &[dna.clone()]).await.unwrap().into_tuple();
+    conductors.exchange_peer_info().await;
+
+    let alice_zome = alice.zome("posts");
+    let bob_zome = bobbo.zome("posts");
+
+    // Alice creates Posts, Bob comments on each of them, and Alice certifies Bob for each
+    // of them in a chain where every Certificate references the previous one
+    let mut posts = vec![];
+    let mut certificates: Vec<Record> = vec![];
+    for i in 0..post_count {
+        let post = create_post(
+            &conductors[0],
+            &alice_zome,
+            Post {
+                title: format!("Post {}", i),
+                needs: vec![],
+                body: String::new(),
+                format: ContentFormat::Plain,
+                attachments: vec![],
+            },
+        )
+        .await;
+        let certifications_hashes = certificates.last().map(entry_hash).into_iter().collect();
+        let certificate = create_certificate(
+            &conductors[0],
+            &alice_zome,
+            certificate(&action_hash(&post), &bobbo, certifications_hashes),
+        )
+        .await;
+        posts.push(post);
+        certificates.push(certificate);
+    }
+
+    await_consistency(Duration::from_secs(120), [&alice, &bobbo])
+        .await
+        .expect("Timed out waiting for consistency of the posts");
+
+    for post in &posts {
+        create_comment(&conductors[1], &bob_zome, Comment { post_hash: action_hash(post) }).await;
+    }
+
+    await_consistency(Duration::from_secs(120), [&alice, &bobbo])
+        .await
+        .expect("Timed out waiting for consistency of the comments");
+
+    // Carol joins
+    let (carol,) = conductors[2].setup_app("posts_test", &[dna]).await.unwrap().into_tuple();
+    conductors.exchange_peer_info().await;
+    let carol_zome = carol.zome("posts");
+
+    await_consistency(Duration::from_secs(300), [&alice, &bobbo, &carol])
+        .await
+        .expect("Timed out waiting for the late joiner to catch up");
+
+    let links: Vec<Link> = conductors[2].call(&carol_zome, "get_all_posts", ()).await;
+    assert_eq!(links.len(), post_count);
+
+    let links: Vec<Link> = conductors[2]
+        .call(&carol_zome, "get_certificates_for_certified", bobbo.agent_pubkey().clone())
+        .await;
+    assert_eq!(links.len(), post_count);
+
+    let links: Vec<Link> = conductors[2]
+        .call(&carol_zome, "get_comments_for_post", action_hash(&posts[0]))
+        .await;
+    assert_eq!(links.len(), 1);
+
+    // Carol builds on the oldest Post and on the end of the certificate chain, which her
+    // own validation has to resolve
+    create_comment(&conductors[2], &carol_zome, Comment { post_hash: action_hash(&posts[0]) }).await;
+    let carol_certificate = create_certificate(
+        &conductors[2],
+        &carol_zome,
+        certificate(
+            &action_hash(&posts[post_count - 1]),
+            &alice,
+            vec![entry_hash(&certificates[0]), entry_hash(certificates.last().unwrap())],
+        ),
+    )
+    .await;
+
+    await_consistency(Duration::from_secs(120), [&alice, &bobbo, &carol])
+        .await
+        .expect("Timed out waiting for consistency after the late joiner's content");
+
+    for (conductor, zome) in [(&conductors[0], &alice_zome), (&conductors[1], &bob_zome)] {
+        let links: Vec<Link> = conductor
+            .call(zome, "get_comments_for_post", action_hash(&posts[0]))
+            .await;
+        assert_eq!(links.len(), 2);
+
+        let links: Vec<Link> = conductor
+            .call(zome, "get_certificates_for_certified", alice.agent_pubkey().clone())
+            .await;
+        assert_eq!(sorted_targets(links), vec![AnyLinkableHash::from(action_hash(&carol_certificate))]);
+    }
+}
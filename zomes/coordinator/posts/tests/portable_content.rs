@@ -0,0 +1,349 @@
+#![allow(dead_code)]
+#![allow(unused_variables)]
+#![allow(unused_imports)]
+
+use hdk::prelude::*;
+use holochain::sweettest::*;
+use std::time::Duration;
+
+use posts_integrity::*;
+
+use posts::post::UpdatePostInput;
+use posts::portable_content::{
+    ExportedContent, ExportedRecord, ImportContentInput, ImportResult, ImportedRecord,
+};
+
+mod common;
+use common::{create_certificate, create_comment, create_like, create_post, posts_dna, sample_post_1, sample_post_2, setup, with_properties};
+
+fn action_hash(record: &Record) -> ActionHash {
+    record.signed_action.hashed.hash.clone()
+}
+
+fn entry_hash(record: &Record) -> EntryHash {
+    record.action().entry_hash().unwrap().clone()
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn exported_content_is_imported_into_another_dna() {
+    // Set up conductors
+    let (mut conductors, alice, bobbo) = setup().await;
+
+    let alice_zome = alice.zome("posts");
+    let bob_zome = bobbo.zome("posts");
+
+    // Alice creates and updates a Post, comments on it, likes Bob and certifies him twice
+    let post = create_post(&conductors[0], &alice_zome, sample_post_1(&conductors[0], &alice_zome).await).await;
+    let update: Record = conductors[0]
+        .call(
+            &alice_zome,
+            "update_post",
+            UpdatePostInput {
+                original_post_hash: action_hash(&post),
+                previous_post_hash: action_hash(&post),
+                updated_post: sample_post_2(&conductors[0], &alice_zome).await,
+            },
+        )
+        .await;
+    let comment = create_comment(&conductors[0], &alice_zome, Comment { post_hash: action_hash(&post) }).await;
+    let like = create_like(
+        &conductors[0],
+        &alice_zome,
+        Like {
+            like_hash: None,
+            agent: bobbo.agent_pubkey().clone(),
+        },
+    )
+    .await;
+    let certificate = |certifications_hashes: Vec<EntryHash>| Certificate {
+        post_hash: action_hash(&post),
+        agent: bobbo.agent_pubkey().clone(),
+        certifications_hashes,
+        certificate_type: CertificateType::TypeOne,
+        dna_hash: alice.cell_id().dna_hash().clone(),
+    };
+    let certificate_a = create_certificate(&conductors[0], &alice_zome, certificate(vec![])).await;
+    let certificate_b = create_certificate(
+        &conductors[0],
+        &alice_zome,
+        certificate(vec![entry_hash(&certificate_a)]),
+    )
+    .await;
+
+    // Bob's content is not part of Alice's export
+    create_comment(&conductors[1], &bob_zome, Comment { post_hash: action_hash(&post) }).await;
+
+    await_consistency(Duration::from_secs(60), [&alice, &bobbo])
+        .await
+        .expect("Timed out waiting for consistency");
+
+    let exported: Vec<ExportedRecord> = conductors[0].call(&alice_zome, "export_my_content", ()).await;
+
+    let exported_hashes: Vec<ActionHash> = exported.iter().map(|record| record.action_hash.clone()).collect();
+    assert_eq!(
+        exported_hashes,
+        vec![
+            action_hash(&post),
+            action_hash(&update),
+            action_hash(&comment),
+            action_hash(&like),
+            action_hash(&certificate_a),
+            action_hash(&certificate_b),
+        ]
+    );
+    assert_eq!(exported[1].previous_action_hash, Some(action_hash(&post)));
+
+    // Alice imports her content into a DNA with a different hash
+    let new_dna = with_properties(posts_dna().await, PostsProperties::default());
+    let apps = conductors.setup_app("posts_test_v2", &[new_dna]).await.unwrap();
+    conductors.exchange_peer_info().await;
+    let ((new_alice,), (new_bobbo,)) = apps.into_tuples();
+    assert_ne!(new_alice.cell_id().dna_hash(), alice.cell_id().dna_hash());
+
+    let new_alice_zome = new_alice.zome("posts");
+    let result: ImportResult = conductors[0]
+        .call(
+            &new_alice_zome,
+            "import_content",
+            ImportContentInput {
+                records: exported.clone(),
+                previously_imported: vec![],
+            },
+        )
+        .await;
+    assert!(result.not_imported.is_empty());
+    assert_eq!(result.remaining, 0);
+    let imported = result.imported;
+    assert_eq!(imported.len(), exported.len());
+
+    let new_hash = |old: &Record| -> ActionHash {
+        imported
+            .iter()
+            .find(|imported| imported.exported_action_hash == action_hash(old))
+            .unwrap()
+            .action_hash
+            .clone()
+    };
+
+    await_consistency(Duration::from_secs(60), [&new_alice, &new_bobbo])
+        .await
+        .expect("Timed out waiting for consistency of the import");
+
+    let new_bob_zome = new_bobbo.zome("posts");
+
+    // The Post keeps its revisions
+    let latest: Option<Record> = conductors[1]
+        .call(&new_bob_zome, "get_latest_post", new_hash(&post))
+        .await;
+    let latest = latest.unwrap();
+    assert_eq!(action_hash(&latest), new_hash(&update));
+    let latest_post: Post = latest.entry().to_app_option().unwrap().unwrap();
+    assert_eq!(latest_post, sample_post_2(&conductors[1], &new_bob_zome).await);
+
+    // The Comment points to the imported Post
+    let links: Vec<Link> = conductors[1]
+        .call(&new_bob_zome, "get_comments_for_post", new_hash(&post))
+        .await;
+    assert_eq!(links.len(), 1);
+    assert_eq!(links[0].target, new_hash(&comment).into());
+
+    // The Certificates point to the imported Post and to each other
+    let new_certificate_b: Option<Record> = conductors[1]
+        .call(&new_bob_zome, "get_certificate", new_hash(&certificate_b))
+        .await;
+    let new_certificate_b: Certificate = new_certificate_b.unwrap().entry().to_app_option().unwrap().unwrap();
+    let new_certificate_a: Option<Record> = conductors[1]
+        .call(&new_bob_zome, "get_certificate", new_hash(&certificate_a))
+        .await;
+    let new_certificate_a = new_certificate_a.unwrap();
+    assert_eq!(new_certificate_b.post_hash, new_hash(&post));
+    assert_eq!(new_certificate_b.certifications_hashes, vec![entry_hash(&new_certificate_a)]);
+    assert_eq!(&new_certificate_b.dna_hash, new_alice.cell_id().dna_hash());
+
+    let links: Vec<Link> = conductors[1]
+        .call(&new_bob_zome, "get_certificates_for_certificate", entry_hash(&new_certificate_a))
+        .await;
+    assert_eq!(links.len(), 1);
+    assert_eq!(links[0].target, new_hash(&certificate_b).into());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn content_referencing_records_outside_the_export_is_not_imported() {
+    // Set up conductors
+    let (mut conductors, alice, bobbo) = setup().await;
+
+    let alice_zome = alice.zome("posts");
+    let bob_zome = bobbo.zome("posts");
+
+    // Alice comments on Bob's Post and creates a Post of her own
+    let bob_post = create_post(&conductors[1], &bob_zome, sample_post_1(&conductors[1], &bob_zome).await).await;
+
+    await_consistency(Duration::from_secs(60), [&alice, &bobbo])
+        .await
+        .expect("Timed out waiting for consistency");
+
+    let comment = create_comment(&conductors[0], &alice_zome, Comment { post_hash: action_hash(&bob_post) }).await;
+    let post = create_post(&conductors[0], &alice_zome, sample_post_2(&conductors[0], &alice_zome).await).await;
+
+    let exported: Vec<ExportedRecord> = conductors[0].call(&alice_zome, "export_my_content", ()).await;
+    assert_eq!(exported.len(), 2);
+
+    // Alice imports her content into a DNA where Bob's Post doesn't exist
+    let new_dna = with_properties(posts_dna().await, PostsProperties::default());
+    let apps = conductors.setup_app("posts_test_v2", &[new_dna]).await.unwrap();
+    conductors.exchange_peer_info().await;
+    let ((new_alice,), (_new_bobbo,)) = apps.into_tuples();
+
+    let new_alice_zome = new_alice.zome("posts");
+    let result: ImportResult = conductors[0]
+        .call(
+            &new_alice_zome,
+            "import_content",
+            ImportContentInput {
+                records: exported,
+                previously_imported: vec![],
+            },
+        )
+        .await;
+
+    assert_eq!(result.imported.len(), 1);
+    assert_eq!(result.imported[0].exported_action_hash, action_hash(&post));
+    assert_eq!(result.not_imported.len(), 1);
+    assert_eq!(result.not_imported[0].exported_action_hash, action_hash(&comment));
+    assert_eq!(
+        result.not_imported[0].reason,
+        format!("References record {}, which was not imported", action_hash(&bob_post))
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn deleted_content_is_not_exported() {
+    // Set up conductors
+    let (conductors, alice, _bobbo) = setup().await;
+
+    let alice_zome = alice.zome("posts");
+
+    // Alice creates two Posts, updates the first one and then deletes it
+    let post = create_post(&conductors[0], &alice_zome, sample_post_1(&conductors[0], &alice_zome).await).await;
+    let _update: Record = conductors[0]
+        .call(
+            &alice_zome,
+            "update_post",
+            UpdatePostInput {
+                original_post_hash: action_hash(&post),
+                previous_post_hash: action_hash(&post),
+                updated_post: sample_post_2(&conductors[0], &alice_zome).await,
+            },
+        )
+        .await;
+    let kept_post = create_post(&conductors[0], &alice_zome, sample_post_2(&conductors[0], &alice_zome).await).await;
+    let _: ActionHash = conductors[0].call(&alice_zome, "delete_post", action_hash(&post)).await;
+
+    let exported: Vec<ExportedRecord> = conductors[0].call(&alice_zome, "export_my_content", ()).await;
+
+    let exported_hashes: Vec<ActionHash> = exported.iter().map(|record| record.action_hash.clone()).collect();
+    assert_eq!(exported_hashes, vec![action_hash(&kept_post)]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn content_is_imported_in_batches_that_respect_the_rate_limit() {
+    // Set up conductors
+    let (mut conductors, alice, _bobbo) = setup().await;
+
+    let alice_zome = alice.zome("posts");
+
+    // Alice creates two Posts and comments on the first one
+    let post_a = create_post(&conductors[0], &alice_zome, sample_post_1(&conductors[0], &alice_zome).await).await;
+    let post_b = create_post(&conductors[0], &alice_zome, sample_post_2(&conductors[0], &alice_zome).await).await;
+    let comment = create_comment(&conductors[0], &alice_zome, Comment { post_hash: action_hash(&post_a) }).await;
+
+    let exported: Vec<ExportedRecord> = conductors[0].call(&alice_zome, "export_my_content", ()).await;
+    assert_eq!(exported.len(), 3);
+
+    // Alice imports her content into a DNA that only allows two creates per hour
+    let new_dna = with_properties(
+        posts_dna().await,
+        PostsProperties {
+            rate_limit: Some(RateLimit {
+                max_creates: 2,
+                window_secs: 3600,
+            }),
+            ..Default::default()
+        },
+    );
+    let apps = conductors.setup_app("posts_test_v2", &[new_dna]).await.unwrap();
+    let ((new_alice,), (_new_bobbo,)) = apps.into_tuples();
+    let new_alice_zome = new_alice.zome("posts");
+
+    // The first batch stops before the third create instead of failing
+    let first: ImportResult = conductors[0]
+        .call(
+            &new_alice_zome,
+            "import_content",
+            ImportContentInput {
+                records: exported.clone(),
+                previously_imported: vec![],
+            },
+        )
+        .await;
+    assert_eq!(first.imported.len(), 2);
+    assert!(first.not_imported.is_empty());
+    assert_eq!(first.remaining, 1);
+
+    // Once the limit is reached, a later batch imports nothing and reports it as remaining
+    let retried: ImportResult = conductors[0]
+        .call(
+            &new_alice_zome,
+            "import_content",
+            ImportContentInput {
+                records: exported[2..].to_vec(),
+                previously_imported: first.imported.clone(),
+            },
+        )
+        .await;
+    assert!(retried.imported.is_empty());
+    assert_eq!(retried.remaining, 1);
+
+    // In a DNA without a rate limit, the remaining record is imported in a second batch and
+    // its reference to a record of the first batch is remapped
+    let unlimited_dna = with_properties(posts_dna().await, PostsProperties::default());
+    let apps = conductors.setup_app("posts_test_v3", &[unlimited_dna]).await.unwrap();
+    let ((unlimited_alice,), (_unlimited_bobbo,)) = apps.into_tuples();
+    let unlimited_zome = unlimited_alice.zome("posts");
+
+    let first: ImportResult = conductors[0]
+        .call(
+            &unlimited_zome,
+            "import_content",
+            ImportContentInput {
+                records: exported[..2].to_vec(),
+                previously_imported: vec![],
+            },
+        )
+        .await;
+    assert_eq!(first.imported.len(), 2);
+    let second: ImportResult = conductors[0]
+        .call(
+            &unlimited_zome,
+            "import_content",
+            ImportContentInput {
+                records: exported[2..].to_vec(),
+                previously_imported: first.imported.clone(),
+            },
+        )
+        .await;
+    assert!(second.not_imported.is_empty());
+    assert_eq!(second.imported.len(), 1);
+    assert_eq!(second.imported[0].exported_action_hash, action_hash(&comment));
+
+    let new_comment: Option<Record> = conductors[0]
+        .call(&unlimited_zome, "get_latest_comment", second.imported[0].action_hash.clone())
+        .await;
+    let new_comment: Comment = new_comment.unwrap().entry().to_app_option().unwrap().unwrap();
+    let new_post_a = first
+        .imported
+        .iter()
+        .find(|imported| imported.exported_action_hash == action_hash(&post_a))
+        .unwrap();
+    assert_eq!(new_comment.post_hash, new_post_a.action_hash);
+}
@@ -0,0 +1,99 @@
+#![allow(dead_code)]
+#![allow(unused_variables)]
+#![allow(unused_imports)]
+
+use hdk::prelude::*;
+use holochain::sweettest::*;
+use std::time::Duration;
+
+use posts_integrity::*;
+
+use posts::post_draft::UpdateDraftInput;
+
+mod common;
+use common::{assert_rejected, sample_post_1, sample_post_2, setup};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn save_update_and_publish_draft() {
+    // Set up conductors
+    let (conductors, alice, bobbo) = setup().await;
+
+    let alice_zome = alice.zome("posts");
+    let bob_zome = bobbo.zome("posts");
+
+    // Alice saves a draft
+    let sample_1 = sample_post_1(&conductors[0], &alice_zome).await;
+    let draft_record: Record = conductors[0]
+        .call(&alice_zome, "save_draft", sample_1.clone())
+        .await;
+    let draft: PostDraft = draft_record.entry().to_app_option().unwrap().unwrap();
+    assert_eq!(draft.post, sample_1);
+
+    // Alice updates the draft
+    let sample_2 = sample_post_2(&conductors[0], &alice_zome).await;
+    let updated_draft_record: Record = conductors[0]
+        .call(
+            &alice_zome,
+            "update_draft",
+            UpdateDraftInput {
+                previous_draft_hash: draft_record.signed_action.hashed.hash.clone(),
+                updated_post: sample_2.clone(),
+            },
+        )
+        .await;
+
+    let drafts: Vec<Record> = conductors[0].call(&alice_zome, "list_drafts", ()).await;
+    assert_eq!(drafts.len(), 1);
+    assert_eq!(drafts[0], updated_draft_record);
+
+    // The draft Alice already updated can't be updated again
+    let result: Result<Record, _> = conductors[0]
+        .call_fallible(
+            &alice_zome,
+            "update_draft",
+            UpdateDraftInput {
+                previous_draft_hash: draft_record.signed_action.hashed.hash.clone(),
+                updated_post: sample_1.clone(),
+            },
+        )
+        .await;
+    assert_rejected(result, "PostDraft not found, or already updated or published");
+
+    await_consistency(Duration::from_secs(60), [&alice, &bobbo])
+        .await
+        .expect("Timed out waiting for consistency");
+
+    // Drafts are not visible to Bob
+    let links: Vec<Link> = conductors[1].call(&bob_zome, "get_all_posts", ()).await;
+    assert_eq!(links.len(), 0);
+
+    // Alice publishes the draft
+    let post_record: Record = conductors[0]
+        .call(
+            &alice_zome,
+            "publish_draft",
+            updated_draft_record.signed_action.hashed.hash.clone(),
+        )
+        .await;
+    let post: Post = post_record.entry().to_app_option().unwrap().unwrap();
+    assert_eq!(post, sample_2);
+
+    let drafts: Vec<Record> = conductors[0].call(&alice_zome, "list_drafts", ()).await;
+    assert_eq!(drafts.len(), 0);
+
+    await_consistency(Duration::from_secs(60), [&alice, &bobbo])
+        .await
+        .expect("Timed out waiting for consistency");
+
+    let links: Vec<Link> = conductors[1].call(&bob_zome, "get_all_posts", ()).await;
+    assert_eq!(links.len(), 1);
+    assert_eq!(
+        links[0].target.clone().into_action_hash().unwrap(),
+        post_record.signed_action.hashed.hash
+    );
+
+    let links: Vec<Link> = conductors[1]
+        .call(&bob_zome, "get_posts_by_author", alice.agent_pubkey().clone())
+        .await;
+    assert_eq!(links.len(), 1);
+}
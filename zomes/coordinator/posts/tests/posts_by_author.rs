@@ -4,26 +4,15 @@
 
 use std::time::Duration;
 use hdk::prelude::*;
-use holochain::{conductor::config::ConductorConfig, sweettest::*};
+use holochain::sweettest::*;
 
 mod common;
-use common::{create_post, sample_post_1};
+use common::{create_post, sample_post_1, setup};
 
 #[tokio::test(flavor = "multi_thread")]
 async fn create_a_post_and_get_posts_by_author() {
-    // Use prebuilt dna file
-    let dna_path = std::env::current_dir()
-        .unwrap()
-        .join(std::env::var("DNA_PATH").expect("DNA_PATH not set, must be run using nix flake check"));
-
-    let dna = SweetDnaFile::from_bundle(&dna_path).await.unwrap();
-
     // Set up conductors
-    let mut conductors = SweetConductorBatch::from_config(2, ConductorConfig::default()).await;
-    let apps = conductors.setup_app("posts_test", &[dna]).await.unwrap();
-    conductors.exchange_peer_info().await;
-
-    let ((alice,), (bobbo,)) = apps.into_tuples();
+    let (conductors, alice, bobbo) = setup().await;
     
     let alice_zome = alice.zome("posts");
     let bob_zome = bobbo.zome("posts");
@@ -0,0 +1,69 @@
+#![allow(dead_code)]
+#![allow(unused_variables)]
+#![allow(unused_imports)]
+
+use hdk::prelude::*;
+use holochain::sweettest::*;
+use std::time::Duration;
+
+use posts_integrity::*;
+
+mod common;
+use common::{create_post, sample_post_1, sample_post_2, setup_with_properties};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn creates_over_the_rate_limit_are_rejected() {
+    // Set up conductors
+    let (conductors, alice, bobbo) = setup_with_properties(PostsProperties {
+        rate_limit: Some(RateLimit {
+            max_creates: 2,
+            window_secs: 3600,
+        }),
+        ..Default::default()
+    }).await;
+
+    let alice_zome = alice.zome("posts");
+    let bob_zome = bobbo.zome("posts");
+
+    // Alice creates a Post and a Comment, reaching her limit
+    let post_record = create_post(&conductors[0], &alice_zome, sample_post_1(&conductors[0], &alice_zome).await).await;
+    let post_hash = post_record.signed_action.hashed.hash.clone();
+    let _: Record = conductors[0]
+        .call(
+            &alice_zome,
+            "create_comment",
+            Comment {
+                post_hash: post_hash.clone(),
+            },
+        )
+        .await;
+
+    let result: Result<Record, _> = conductors[0]
+        .call_fallible(&alice_zome, "create_post", sample_post_2(&conductors[0], &alice_zome).await)
+        .await;
+    let error = result.unwrap_err();
+    assert!(format!("{error:?}").contains("Rate limit exceeded"));
+
+    let result: Result<Record, _> = conductors[0]
+        .call_fallible(
+            &alice_zome,
+            "create_comment",
+            Comment {
+                post_hash: post_hash.clone(),
+            },
+        )
+        .await;
+    assert!(result.is_err());
+
+    // Bob has his own limit
+    await_consistency(Duration::from_secs(60), [&alice, &bobbo])
+        .await
+        .expect("Timed out waiting for consistency");
+
+    let _: Record = conductors[1]
+        .call(&bob_zome, "create_comment", Comment { post_hash })
+        .await;
+
+    let links: Vec<Link> = conductors[1].call(&bob_zome, "get_all_posts", ()).await;
+    assert_eq!(links.len(), 1);
+}
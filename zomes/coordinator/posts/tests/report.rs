@@ -0,0 +1,125 @@
+#![allow(dead_code)]
+#![allow(unused_variables)]
+#![allow(unused_imports)]
+
+use hdk::prelude::*;
+use holochain::sweettest::*;
+use std::time::Duration;
+
+use posts_integrity::*;
+
+use posts::pagination::Page;
+use posts::report::ReportedItem;
+
+mod common;
+use common::{assert_rejected, create_comment, create_post, sample_post_1, setup};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn report_posts_and_comments() {
+    // Set up conductors
+    let (conductors, alice, bobbo) = setup().await;
+
+    let alice_zome = alice.zome("posts");
+    let bob_zome = bobbo.zome("posts");
+
+    // Alice creates a Post and a Comment on it
+    let post_record = create_post(&conductors[0], &alice_zome, sample_post_1(&conductors[0], &alice_zome).await).await;
+    let post_hash = post_record.signed_action.hashed.hash.clone();
+    let comment_record = create_comment(
+        &conductors[0],
+        &alice_zome,
+        Comment {
+            post_hash: post_hash.clone(),
+        },
+    )
+    .await;
+    let comment_hash = comment_record.signed_action.hashed.hash.clone();
+
+    await_consistency(Duration::from_secs(60), [&alice, &bobbo])
+        .await
+        .expect("Timed out waiting for consistency");
+
+    // Both agents report the Post, Bob also reports the Comment
+    let _: Record = conductors[0]
+        .call(
+            &alice_zome,
+            "create_report",
+            Report {
+                target_hash: post_hash.clone(),
+                reason: ReportReason::Misinformation,
+            },
+        )
+        .await;
+    let _: Record = conductors[1]
+        .call(
+            &bob_zome,
+            "create_report",
+            Report {
+                target_hash: post_hash.clone(),
+                reason: ReportReason::Spam,
+            },
+        )
+        .await;
+    let _: Record = conductors[1]
+        .call(
+            &bob_zome,
+            "create_report",
+            Report {
+                target_hash: comment_hash.clone(),
+                reason: ReportReason::Other {
+                    description: "Off topic".to_string(),
+                },
+            },
+        )
+        .await;
+
+    // Bob can't report the same Post twice
+    let result: Result<Record, _> = conductors[1]
+        .call_fallible(
+            &bob_zome,
+            "create_report",
+            Report {
+                target_hash: post_hash.clone(),
+                reason: ReportReason::Harassment,
+            },
+        )
+        .await;
+    assert_rejected(result, "An agent can only report the same item once");
+
+    await_consistency(Duration::from_secs(60), [&alice, &bobbo])
+        .await
+        .expect("Timed out waiting for consistency");
+
+    let links: Vec<Link> = conductors[0]
+        .call(&alice_zome, "get_reports_for", post_hash.clone())
+        .await;
+    assert_eq!(links.len(), 2);
+
+    let links: Vec<Link> = conductors[0]
+        .call(&alice_zome, "get_reports_for", comment_hash.clone())
+        .await;
+    assert_eq!(links.len(), 1);
+
+    let most_reported: Vec<ReportedItem> = conductors[0]
+        .call(&alice_zome, "get_most_reported", Page { page: 0, page_size: 10 })
+        .await;
+    assert_eq!(
+        most_reported,
+        vec![
+            ReportedItem {
+                target_hash: post_hash,
+                reports_count: 2,
+            },
+            ReportedItem {
+                target_hash: comment_hash,
+                reports_count: 1,
+            },
+        ]
+    );
+
+    let second_page: Vec<ReportedItem> = conductors[0]
+        .call(&alice_zome, "get_most_reported", Page { page: 1, page_size: 1 })
+        .await;
+    assert_eq!(second_page.len(), 1);
+    assert_eq!(second_page[0].reports_count, 1);
+}
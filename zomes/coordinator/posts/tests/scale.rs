@@ -0,0 +1,256 @@
+#![allow(dead_code)]
+#![allow(unused_variables)]
+#![allow(unused_imports)]
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use hdk::prelude::*;
+use holochain::conductor::config::ConductorConfig;
+use holochain::prelude::DatabaseResult;
+use holochain::sweettest::*;
+
+use posts_integrity::*;
+
+mod common;
+use common::{create_certificate, create_comment, create_like, create_post, posts_dna, sample_post_1};
+
+// The scenario is sized through environment variables, e.g.
+// POSTS_SCALE_CONDUCTORS=20 POSTS_SCALE_AGENTS_PER_CONDUCTOR=2 cargo test --profile release --test scale -- --ignored
+// and writes its report to POSTS_SCALE_REPORT, or to posts_scale_report.json in Cargo's
+// temporary directory for integration tests when unset
+fn env_or(name: &str, default: usize) -> usize {
+    std::env::var(name)
+        .ok()
+        .map(|value| value.parse().unwrap_or_else(|_| panic!("{} must be a number", name)))
+        .unwrap_or(default)
+}
+
+#[derive(Serialize, Debug)]
+struct PhaseReport {
+    name: String,
+    items_created: usize,
+    workload_ms: u128,
+    time_to_consistency_ms: u128,
+    max_validation_queue_depth: i64,
+    validation_queue_depth_samples: Vec<i64>,
+}
+
+#[derive(Serialize, Debug)]
+struct ScaleReport {
+    conductors: usize,
+    agents_per_conductor: usize,
+    posts_per_agent: usize,
+    phases: Vec<PhaseReport>,
+}
+
+struct Agent {
+    conductor: usize,
+    cell: SweetCell,
+}
+
+// Ops that have been received but not integrated yet, summed over all conductors
+async fn validation_queue_depth(conductors: &SweetConductorBatch, dna_hash: &DnaHash) -> i64 {
+    let mut depth = 0;
+    for conductor in conductors.iter() {
+        let db = conductor.get_dht_db(dna_hash).unwrap();
+        depth += db
+            .read_async(|txn| -> DatabaseResult<i64> {
+                Ok(txn.query_row(
+                    "SELECT COUNT(*) FROM DhtOp WHERE when_integrated IS NULL",
+                    (),
+                    |row| row.get(0),
+                )?)
+            })
+            .await
+            .unwrap();
+    }
+    depth
+}
+
+// Runs the phase's workload on every agent concurrently, then waits for all cells to be
+// consistent while sampling the validation queue
+async fn run_phase<F, Fut>(
+    name: &str,
+    conductors: &SweetConductorBatch,
+    agents: &[Agent],
+    workload: F,
+) -> (PhaseReport, Vec<Vec<Record>>)
+where
+    F: Fn(usize) -> Fut,
+    Fut: std::future::Future<Output = Vec<Record>>,
+{
+    let dna_hash = agents[0].cell.cell_id().dna_hash().clone();
+
+    let started = Instant::now();
+    let records = futures::future::join_all((0..agents.len()).map(workload)).await;
+    let workload_ms = started.elapsed().as_millis();
+
+    let consistent = AtomicBool::new(false);
+    let started = Instant::now();
+    let (result, samples) = tokio::join!(
+        async {
+            let result = await_consistency(
+                Duration::from_secs(600),
+                agents.iter().map(|agent| &agent.cell),
+            )
+            .await;
+            consistent.store(true, Ordering::SeqCst);
+            result
+        },
+        async {
+            let mut samples = vec![];
+            while !consistent.load(Ordering::SeqCst) {
+                samples.push(validation_queue_depth(conductors, &dna_hash).await);
+                tokio::time::sleep(Duration::from_millis(500)).await;
+            }
+            samples
+        },
+    );
+    let time_to_consistency_ms = started.elapsed().as_millis();
+    result.unwrap_or_else(|e| panic!("Timed out waiting for consistency in phase {}: {}", name, e));
+
+    let report = PhaseReport {
+        name: name.to_string(),
+        items_created: records.iter().map(|records| records.len()).sum(),
+        workload_ms,
+        time_to_consistency_ms,
+        max_validation_queue_depth: samples.iter().copied().max().unwrap_or(0),
+        validation_queue_depth_samples: samples,
+    };
+    (report, records)
+}
+
+fn certificate(post_hash: &ActionHash, agent: &SweetCell, certifications_hashes: Vec<EntryHash>) -> Certificate {
+    Certificate {
+        post_hash: post_hash.clone(),
+        agent: agent.agent_pubkey().clone(),
+        certifications_hashes,
+        certificate_type: CertificateType::TypeOne,
+        dna_hash: agent.cell_id().dna_hash().clone(),
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[ignore = "scale scenario, run explicitly with --ignored"]
+async fn concurrent_content_across_many_conductors_reaches_consistency() {
+    let conductor_count = env_or("POSTS_SCALE_CONDUCTORS", 10);
+    let agents_per_conductor = env_or("POSTS_SCALE_AGENTS_PER_CONDUCTOR", 1);
+    let posts_per_agent = env_or("POSTS_SCALE_POSTS_PER_AGENT", 5);
+
+    // Set up conductors, with one app per agent
+    let dna = posts_dna().await;
+    let mut conductors =
+        SweetConductorBatch::from_config(conductor_count, ConductorConfig::default()).await;
+    let mut agents = vec![];
+    for conductor in 0..conductor_count {
+        for i in 0..agents_per_conductor {
+            let app = conductors[conductor]
+                .setup_app(&format!("posts_scale_{}", i), &[dna.clone()])
+                .await
+                .unwrap();
+            let (cell,) = app.into_tuple();
+            agents.push(Agent { conductor, cell });
+        }
+    }
+    conductors.exchange_peer_info().await;
+
+    let next = |i: usize| &agents[(i + 1) % agents.len()];
+    let mut phases = vec![];
+
+    // Every agent creates posts, comments on each of them and likes the next agent
+    let (report, content) = run_phase("content", &conductors, &agents, |i| {
+        let agent = &agents[i];
+        let conductor = &conductors[agent.conductor];
+        let zome = agent.cell.zome("posts");
+        let liked = next(i).cell.agent_pubkey().clone();
+        async move {
+            let mut records = vec![];
+            for _ in 0..posts_per_agent {
+                let post = create_post(conductor, &zome, sample_post_1(conductor, &zome).await).await;
+                let comment = create_comment(
+                    conductor,
+                    &zome,
+                    Comment {
+                        post_hash: post.signed_action.hashed.hash.clone(),
+                    },
+                )
+                .await;
+                let like = create_like(
+                    conductor,
+                    &zome,
+                    Like {
+                        like_hash: None,
+                        agent: liked.clone(),
+                    },
+                )
+                .await;
+                records.extend([post, comment, like]);
+            }
+            records
+        }
+    })
+    .await;
+    phases.push(report);
+
+    // Every agent certifies the next one for its first post
+    let (report, certificates) = run_phase("certificates", &conductors, &agents, |i| {
+        let agent = &agents[i];
+        let conductor = &conductors[agent.conductor];
+        let zome = agent.cell.zome("posts");
+        let post_hash = content[i][0].signed_action.hashed.hash.clone();
+        let certified = &next(i).cell;
+        async move {
+            vec![create_certificate(conductor, &zome, certificate(&post_hash, certified, vec![])).await]
+        }
+    })
+    .await;
+    phases.push(report);
+
+    // Every agent certifies the next one again, referencing the certificate it received
+    // from the previous agent, so that validation has to fetch entries authored elsewhere
+    let (report, _) = run_phase("certificate_chains", &conductors, &agents, |i| {
+        let agent = &agents[i];
+        let conductor = &conductors[agent.conductor];
+        let zome = agent.cell.zome("posts");
+        let post_hash = content[i][0].signed_action.hashed.hash.clone();
+        let certified = &next(i).cell;
+        let received = &certificates[(i + agents.len() - 1) % agents.len()][0];
+        let received_hash = received.action().entry_hash().unwrap().clone();
+        async move {
+            vec![
+                create_certificate(conductor, &zome, certificate(&post_hash, certified, vec![received_hash]))
+                    .await,
+            ]
+        }
+    })
+    .await;
+    phases.push(report);
+
+    let report = ScaleReport {
+        conductors: conductor_count,
+        agents_per_conductor,
+        posts_per_agent,
+        phases,
+    };
+    let path = std::env::var("POSTS_SCALE_REPORT")
+        .unwrap_or(format!("{}/posts_scale_report.json", env!("CARGO_TARGET_TMPDIR")));
+    std::fs::write(&path, serde_json::to_string_pretty(&report).unwrap()).unwrap();
+
+    // Nothing was rejected or abandoned along the way
+    let dna_hash = dna.dna_hash().clone();
+    for conductor in conductors.iter() {
+        let db = conductor.get_dht_db(&dna_hash).unwrap();
+        let invalid_ops = db
+            .read_async(|txn| -> DatabaseResult<i64> {
+                Ok(txn.query_row(
+                    "SELECT COUNT(*) FROM DhtOp WHERE validation_status != 0",
+                    (),
+                    |row| row.get(0),
+                )?)
+            })
+            .await
+            .unwrap();
+        assert_eq!(invalid_ops, 0);
+    }
+}
@@ -0,0 +1,208 @@
+#![allow(dead_code)]
+#![allow(unused_variables)]
+#![allow(unused_imports)]
+
+use hdk::prelude::*;
+use holochain::sweettest::*;
+use std::time::Duration;
+
+use posts_integrity::*;
+
+use posts::scheduled_post::SchedulePostInput;
+
+mod common;
+use common::{assert_rejected, create_post, sample_post_1, setup_with_properties};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn scheduled_post_is_published_after_due_time() {
+    // Set up conductors
+    let (conductors, alice, bobbo) = setup_with_properties(PostsProperties {
+        scheduled_posts_interval_secs: 1,
+        ..Default::default()
+    }).await;
+
+    let alice_zome = alice.zome("posts");
+    let bob_zome = bobbo.zome("posts");
+
+    // Alice schedules a Post 5 seconds in the future
+    let sample = sample_post_1(&conductors[0], &alice_zome).await;
+    let publish_at = (Timestamp::now() + Duration::from_secs(5)).unwrap();
+    let _: Record = conductors[0]
+        .call(
+            &alice_zome,
+            "schedule_post",
+            SchedulePostInput {
+                post: sample.clone(),
+                publish_at,
+            },
+        )
+        .await;
+
+    let links: Vec<Link> = conductors[0].call(&alice_zome, "get_all_posts", ()).await;
+    assert_eq!(links.len(), 0);
+
+    // Wait until the scheduler has published it
+    let mut links: Vec<Link> = vec![];
+    for _ in 0..30 {
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        links = conductors[0].call(&alice_zome, "get_all_posts", ()).await;
+        if !links.is_empty() {
+            break;
+        }
+    }
+    assert_eq!(links.len(), 1);
+    assert!(Timestamp::now() >= publish_at);
+    assert!(links[0].timestamp >= publish_at);
+
+    let scheduled: Vec<Record> = conductors[0]
+        .call(&alice_zome, "get_scheduled_posts", ())
+        .await;
+    assert_eq!(scheduled.len(), 0);
+
+    await_consistency(Duration::from_secs(60), [&alice, &bobbo])
+        .await
+        .expect("Timed out waiting for consistency");
+
+    let links: Vec<Link> = conductors[1].call(&bob_zome, "get_all_posts", ()).await;
+    assert_eq!(links.len(), 1);
+    let post_record: Option<Record> = conductors[1]
+        .call(
+            &bob_zome,
+            "get_original_post",
+            links[0].target.clone().into_action_hash().unwrap(),
+        )
+        .await;
+    let post: Post = post_record.unwrap().entry().to_app_option().unwrap().unwrap();
+    assert_eq!(post, sample);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn posts_that_could_not_be_published_cannot_be_scheduled() {
+    // Set up conductors
+    let (conductors, alice, bobbo) = setup_with_properties(PostsProperties {
+        rate_limit: Some(RateLimit {
+            max_creates: 1,
+            window_secs: 60,
+        }),
+        ..Default::default()
+    }).await;
+
+    let alice_zome = alice.zome("posts");
+
+    let sample = sample_post_1(&conductors[0], &alice_zome).await;
+    let publish_at = (Timestamp::now() + Duration::from_secs(3600)).unwrap();
+
+    // A Post whose fields are out of limits is rejected right away
+    let result: Result<Record, _> = conductors[0]
+        .call_fallible(
+            &alice_zome,
+            "schedule_post",
+            SchedulePostInput {
+                post: Post {
+                    title: String::new(),
+                    ..sample.clone()
+                },
+                publish_at,
+            },
+        )
+        .await;
+    assert_rejected(
+        result,
+        "Post title is 0 characters long, but must be between 1 and 200 characters long",
+    );
+
+    // So is a second Post due within the rate limit window of the first
+    let _: Record = conductors[0]
+        .call(
+            &alice_zome,
+            "schedule_post",
+            SchedulePostInput {
+                post: sample.clone(),
+                publish_at,
+            },
+        )
+        .await;
+    let result: Result<Record, _> = conductors[0]
+        .call_fallible(
+            &alice_zome,
+            "schedule_post",
+            SchedulePostInput {
+                post: sample.clone(),
+                publish_at: (publish_at + Duration::from_secs(30)).unwrap(),
+            },
+        )
+        .await;
+    assert_rejected(
+        result,
+        "Rate limit exceeded: at most 1 posts can be scheduled within 60 seconds of each other",
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn scheduled_posts_that_fail_to_publish_stay_pending_until_cancelled() {
+    // Set up conductors
+    let (conductors, alice, bobbo) = setup_with_properties(PostsProperties {
+        scheduled_posts_interval_secs: 1,
+        rate_limit: Some(RateLimit {
+            max_creates: 1,
+            window_secs: 3600,
+        }),
+        ..Default::default()
+    }).await;
+
+    let alice_zome = alice.zome("posts");
+
+    // Alice schedules two Posts far enough apart, and then uses up her rate limit by hand
+    let sample = sample_post_1(&conductors[0], &alice_zome).await;
+    for hours in [0, 2] {
+        let _: Record = conductors[0]
+            .call(
+                &alice_zome,
+                "schedule_post",
+                SchedulePostInput {
+                    post: sample.clone(),
+                    publish_at: (Timestamp::now() + Duration::from_secs(hours * 3600 + 2))
+                        .unwrap(),
+                },
+            )
+            .await;
+    }
+    create_post(&conductors[0], &alice_zome, sample.clone()).await;
+
+    // The due Post keeps failing the rate limit, but stays scheduled and is retried
+    tokio::time::sleep(Duration::from_secs(5)).await;
+    let scheduled: Vec<Record> = conductors[0]
+        .call(&alice_zome, "get_scheduled_posts", ())
+        .await;
+    assert_eq!(scheduled.len(), 2);
+
+    // Cancelling it does not delete anything but a pending ScheduledPost
+    let links: Vec<Link> = conductors[0].call(&alice_zome, "get_all_posts", ()).await;
+    let result: Result<ActionHash, _> = conductors[0]
+        .call_fallible(
+            &alice_zome,
+            "cancel_scheduled_post",
+            links[0].target.clone().into_action_hash().unwrap(),
+        )
+        .await;
+    assert_rejected(result, "is not a pending ScheduledPost");
+
+    let due = scheduled
+        .iter()
+        .min_by_key(|record| {
+            record.entry().to_app_option::<ScheduledPost>().unwrap().unwrap().publish_at
+        })
+        .unwrap();
+    let _: ActionHash = conductors[0]
+        .call(&alice_zome, "cancel_scheduled_post", due.action_address().clone())
+        .await;
+    let result: Result<ActionHash, _> = conductors[0]
+        .call_fallible(&alice_zome, "cancel_scheduled_post", due.action_address().clone())
+        .await;
+    assert_rejected(result, "is not a pending ScheduledPost");
+
+    let scheduled: Vec<Record> = conductors[0]
+        .call(&alice_zome, "get_scheduled_posts", ())
+        .await;
+    assert_eq!(scheduled.len(), 1);
+}
@@ -0,0 +1,86 @@
+#![allow(dead_code)]
+#![allow(unused_variables)]
+#![allow(unused_imports)]
+
+use hdk::prelude::*;
+use holochain::sweettest::*;
+use std::time::Duration;
+
+use posts_integrity::*;
+
+mod common;
+use common::{create_comment, create_post, sample_post_1, sample_post_2, setup};
+
+async fn collect_signals(
+    signals: &mut tokio::sync::broadcast::Receiver<holochain::prelude::Signal>,
+) -> Vec<posts::Signal> {
+    let mut received = vec![];
+    while let Ok(Ok(signal)) = tokio::time::timeout(Duration::from_secs(2), signals.recv()).await {
+        if let holochain::prelude::Signal::App { signal, .. } = signal {
+            if let Ok(signal) = signal.into_inner().decode::<posts::Signal>() {
+                received.push(signal);
+            }
+        }
+    }
+    received
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn subscription_filters_signals() {
+    // Set up conductors
+    let (conductors, alice, _bobbo) = setup().await;
+
+    let alice_zome = alice.zome("posts");
+
+    // Alice only wants to hear about Comments
+    let _: ActionHash = conductors[0]
+        .call(
+            &alice_zome,
+            "subscribe",
+            Subscription {
+                entry_types: Some(vec![UnitEntryTypes::Comment]),
+                link_types: Some(vec![]),
+                post_hashes: None,
+            },
+        )
+        .await;
+
+    let mut alice_signals = conductors[0].subscribe_to_app_signals("posts_test".into());
+
+    let post_record = create_post(&conductors[0], &alice_zome, sample_post_1(&conductors[0], &alice_zome).await).await;
+    let comment_record = create_comment(
+        &conductors[0],
+        &alice_zome,
+        Comment {
+            post_hash: post_record.signed_action.hashed.hash.clone(),
+        },
+    )
+    .await;
+
+    let signals = collect_signals(&mut alice_signals).await;
+    assert_eq!(signals.len(), 1);
+    match &signals[0] {
+        posts::Signal::EntryCreated { action, app_entry: EntryTypes::Comment(_) } => {
+            assert_eq!(action.hashed.hash, comment_record.signed_action.hashed.hash);
+        }
+        signal => panic!("Unexpected signal {signal:?}"),
+    }
+
+    // After unsubscribing Alice receives every signal again
+    let _: () = conductors[0].call(&alice_zome, "unsubscribe", ()).await;
+    let subscription: Option<Subscription> = conductors[0]
+        .call(&alice_zome, "get_subscription", ())
+        .await;
+    assert!(subscription.is_none());
+
+    let post_record = create_post(&conductors[0], &alice_zome, sample_post_2(&conductors[0], &alice_zome).await).await;
+
+    let signals = collect_signals(&mut alice_signals).await;
+    assert!(signals.iter().any(|signal| matches!(
+        signal,
+        posts::Signal::EntryCreated { app_entry: EntryTypes::Post(_), .. }
+    )));
+    assert!(signals
+        .iter()
+        .any(|signal| matches!(signal, posts::Signal::LinkCreated { .. })));
+}
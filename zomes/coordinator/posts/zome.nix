@@ -27,6 +27,7 @@
               origin_time: 1709638576394039
               zomes: 
                 - name: posts_integrity
+                - name: file_storage_integrity
             coordinator:
               zomes:
                 - name: posts
@@ -34,6 +35,11 @@
                   dependencies: 
                     - name: posts_integrity
                   dylib: ~
+                - name: file_storage
+                  hash: ~
+                  dependencies: 
+                    - name: file_storage_integrity
+                  dylib: ~
           '';
           zomes = inputs.hc-infra.outputs.lib.filterZomes self'.packages;
           holochain = inputs'.holochain;
@@ -0,0 +1,44 @@
+// Test-only coordinator zome: commits entries and links exactly as given, without any of the
+// checks or side effects of the posts coordinator, so that tests can author invalid ops
+use hdk::prelude::*;
+use posts_integrity::*;
+#[hdk_extern]
+pub fn create_raw_entry(entry: EntryTypes) -> ExternResult<ActionHash> {
+    create_entry(&entry)
+}
+#[derive(Serialize, Deserialize)]
+pub struct UpdateRawEntryInput {
+    pub original_action_hash: ActionHash,
+    pub entry: EntryTypes,
+}
+#[hdk_extern]
+pub fn update_raw_entry(input: UpdateRawEntryInput) -> ExternResult<ActionHash> {
+    update_entry(input.original_action_hash, &input.entry)
+}
+#[hdk_extern]
+pub fn delete_raw_entry(action_hash: ActionHash) -> ExternResult<ActionHash> {
+    delete_entry(action_hash)
+}
+#[derive(Serialize, Deserialize)]
+pub struct CreateRawLinkInput {
+    pub base_address: AnyLinkableHash,
+    pub target_address: AnyLinkableHash,
+    pub link_type: LinkTypes,
+    pub tag: Vec<u8>,
+}
+#[hdk_extern]
+pub fn create_raw_link(input: CreateRawLinkInput) -> ExternResult<ActionHash> {
+    create_link(input.base_address, input.target_address, input.link_type, input.tag)
+}
+#[hdk_extern]
+pub fn delete_raw_link(create_link_hash: ActionHash) -> ExternResult<ActionHash> {
+    delete_link(create_link_hash)
+}
+#[hdk_extern]
+pub fn get_full_agent_activity(agent: AgentPubKey) -> ExternResult<AgentActivity> {
+    get_agent_activity(agent, ChainQueryFilter::new(), ActivityRequest::Full)
+}
+#[hdk_extern]
+pub fn query_my_chain() -> ExternResult<Vec<Record>> {
+    query(ChainQueryFilter::new())
+}
@@ -0,0 +1,26 @@
+use hdi::prelude::*;
+use file_storage_types::*;
+pub fn validate_create_file_chunk(
+    _action: EntryCreationAction,
+    file_chunk: FileChunk,
+) -> ExternResult<ValidateCallbackResult> {
+    if let Some(error) = check_file_chunk(&file_chunk) {
+        return Ok(ValidateCallbackResult::Invalid(error));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_update_file_chunk(
+    _action: Update,
+    _file_chunk: FileChunk,
+    _original_action: EntryCreationAction,
+    _original_file_chunk: FileChunk,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from("FileChunks cannot be updated")))
+}
+pub fn validate_delete_file_chunk(
+    _action: Delete,
+    _original_action: EntryCreationAction,
+    _original_file_chunk: FileChunk,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Valid)
+}
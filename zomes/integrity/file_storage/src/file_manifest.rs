@@ -0,0 +1,45 @@
+use hdi::prelude::*;
+use file_storage_types::*;
+pub fn validate_create_file_manifest(
+    _action: EntryCreationAction,
+    file_manifest: FileManifest,
+) -> ExternResult<ValidateCallbackResult> {
+    if let Some(error) = check_file_manifest_fields(&file_manifest) {
+        return Ok(ValidateCallbackResult::Invalid(error));
+    }
+    let mut size = 0;
+    for chunk_hash in file_manifest.chunks {
+        let entry = must_get_entry(chunk_hash)?.content;
+        let file_chunk = FileChunk::try_from(entry)?;
+        if let Some(error) = check_file_chunk(&file_chunk) {
+            return Ok(ValidateCallbackResult::Invalid(error));
+        }
+        size += file_chunk.0.bytes().len();
+    }
+    if size != file_manifest.size {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                format!(
+                    "FileManifest declares a size of {} bytes, but its chunks add up to {}",
+                    file_manifest.size, size
+                ),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_update_file_manifest(
+    _action: Update,
+    _file_manifest: FileManifest,
+    _original_action: EntryCreationAction,
+    _original_file_manifest: FileManifest,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from("FileManifests cannot be updated")))
+}
+pub fn validate_delete_file_manifest(
+    _action: Delete,
+    _original_action: EntryCreationAction,
+    _original_file_manifest: FileManifest,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Valid)
+}
@@ -0,0 +1,202 @@
+pub mod file_manifest;
+pub use file_manifest::*;
+pub mod file_chunk;
+use hdi::prelude::*;
+pub use file_chunk::*;
+pub use file_storage_types::*;
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type")]
+#[hdk_entry_types]
+#[unit_enum(UnitEntryTypes)]
+pub enum EntryTypes {
+    FileChunk(FileChunk),
+    FileManifest(FileManifest),
+}
+#[hdk_extern]
+pub fn genesis_self_check(_data: GenesisSelfCheckData) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Valid)
+}
+#[hdk_extern]
+pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
+    match op.flattened::<EntryTypes, ()>()? {
+        FlatOp::StoreEntry(store_entry) => match store_entry {
+            OpEntry::CreateEntry { app_entry, action } => match app_entry {
+                EntryTypes::FileChunk(file_chunk) => {
+                    validate_create_file_chunk(EntryCreationAction::Create(action), file_chunk)
+                }
+                EntryTypes::FileManifest(file_manifest) => {
+                    validate_create_file_manifest(
+                        EntryCreationAction::Create(action),
+                        file_manifest,
+                    )
+                }
+            },
+            OpEntry::UpdateEntry {
+                app_entry, action, ..
+            } => match app_entry {
+                EntryTypes::FileChunk(file_chunk) => {
+                    validate_create_file_chunk(EntryCreationAction::Update(action), file_chunk)
+                }
+                EntryTypes::FileManifest(file_manifest) => {
+                    validate_create_file_manifest(
+                        EntryCreationAction::Update(action),
+                        file_manifest,
+                    )
+                }
+            },
+            _ => Ok(ValidateCallbackResult::Valid),
+        },
+        FlatOp::StoreRecord(store_record) => match store_record {
+            OpRecord::CreateEntry { app_entry, action } => match app_entry {
+                EntryTypes::FileChunk(file_chunk) => {
+                    validate_create_file_chunk(EntryCreationAction::Create(action), file_chunk)
+                }
+                EntryTypes::FileManifest(file_manifest) => {
+                    validate_create_file_manifest(
+                        EntryCreationAction::Create(action),
+                        file_manifest,
+                    )
+                }
+            },
+            OpRecord::UpdateEntry {
+                original_action_hash,
+                app_entry,
+                action,
+                ..
+            } => {
+                let original_record = must_get_valid_record(original_action_hash)?;
+                let original_action = original_record.action().clone();
+                let original_action = match original_action {
+                    Action::Create(create) => EntryCreationAction::Create(create),
+                    Action::Update(update) => EntryCreationAction::Update(update),
+                    _ => {
+                        return Ok(ValidateCallbackResult::Invalid(
+                            "Original action for an update must be a Create or Update action"
+                                .to_string(),
+                        ));
+                    }
+                };
+                match app_entry {
+                    EntryTypes::FileChunk(file_chunk) => {
+                        let original_file_chunk: Option<FileChunk> = original_record
+                            .entry()
+                            .to_app_option()
+                            .map_err(|e| wasm_error!(e))?;
+                        let original_file_chunk = match original_file_chunk {
+                            Some(file_chunk) => file_chunk,
+                            None => {
+                                return Ok(
+                                        ValidateCallbackResult::Invalid(
+                                            "The updated entry type must be the same as the original entry type"
+                                                .to_string(),
+                                        ),
+                                    );
+                            }
+                        };
+                        validate_update_file_chunk(
+                            action,
+                            file_chunk,
+                            original_action,
+                            original_file_chunk,
+                        )
+                    }
+                    EntryTypes::FileManifest(file_manifest) => {
+                        let original_file_manifest: Option<FileManifest> = original_record
+                            .entry()
+                            .to_app_option()
+                            .map_err(|e| wasm_error!(e))?;
+                        let original_file_manifest = match original_file_manifest {
+                            Some(file_manifest) => file_manifest,
+                            None => {
+                                return Ok(
+                                        ValidateCallbackResult::Invalid(
+                                            "The updated entry type must be the same as the original entry type"
+                                                .to_string(),
+                                        ),
+                                    );
+                            }
+                        };
+                        validate_update_file_manifest(
+                            action,
+                            file_manifest,
+                            original_action,
+                            original_file_manifest,
+                        )
+                    }
+                }
+            }
+            OpRecord::DeleteEntry {
+                original_action_hash,
+                action,
+                ..
+            } => {
+                let original_record = must_get_valid_record(original_action_hash)?;
+                let original_action = original_record.action().clone();
+                let original_action = match original_action {
+                    Action::Create(create) => EntryCreationAction::Create(create),
+                    Action::Update(update) => EntryCreationAction::Update(update),
+                    _ => {
+                        return Ok(ValidateCallbackResult::Invalid(
+                            "Original action for a delete must be a Create or Update action"
+                                .to_string(),
+                        ));
+                    }
+                };
+                let app_entry_type = match original_action.entry_type() {
+                    EntryType::App(app_entry_type) => app_entry_type,
+                    _ => {
+                        return Ok(ValidateCallbackResult::Valid);
+                    }
+                };
+                let entry = match original_record.entry().as_option() {
+                    Some(entry) => entry,
+                    None => {
+                        return Ok(
+                            ValidateCallbackResult::Invalid(
+                                "Original record for a delete of a public entry must contain an entry"
+                                    .to_string(),
+                            ),
+                        );
+                    }
+                };
+                let original_app_entry = match EntryTypes::deserialize_from_type(
+                    app_entry_type.zome_index,
+                    app_entry_type.entry_index,
+                    entry,
+                )? {
+                    Some(app_entry) => app_entry,
+                    None => {
+                        return Ok(
+                            ValidateCallbackResult::Invalid(
+                                "Original app entry must be one of the defined entry types for this zome"
+                                    .to_string(),
+                            ),
+                        );
+                    }
+                };
+                match original_app_entry {
+                    EntryTypes::FileChunk(original_file_chunk) => {
+                        validate_delete_file_chunk(action, original_action, original_file_chunk)
+                    }
+                    EntryTypes::FileManifest(original_file_manifest) => {
+                        validate_delete_file_manifest(
+                            action,
+                            original_action,
+                            original_file_manifest,
+                        )
+                    }
+                }
+            }
+            _ => Ok(ValidateCallbackResult::Valid),
+        },
+        _ => Ok(ValidateCallbackResult::Valid),
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn file_manifest_entry_index_matches_the_entry_types() {
+        assert_eq!(UnitEntryTypes::FileManifest as u8, FILE_MANIFEST_ENTRY_INDEX);
+    }
+}
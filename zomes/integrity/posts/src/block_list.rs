@@ -0,0 +1,28 @@
+use hdi::prelude::*;
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq, Default)]
+pub struct BlockList {
+    pub blocked_agents: Vec<AgentPubKey>,
+    pub muted_agents: Vec<AgentPubKey>,
+}
+pub fn validate_create_block_list(
+    _action: EntryCreationAction,
+    _block_list: BlockList,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_update_block_list(
+    _action: Update,
+    _block_list: BlockList,
+    _original_action: EntryCreationAction,
+    _original_block_list: BlockList,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_delete_block_list(
+    _action: Delete,
+    _original_action: EntryCreationAction,
+    _original_block_list: BlockList,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Valid)
+}
@@ -7,6 +7,7 @@ pub enum CertificateType {
 }
 #[hdk_entry_helper]
 #[derive(Clone, PartialEq)]
+#[serde(from = "VersionedCertificate")]
 pub struct Certificate {
     pub post_hash: ActionHash,
     pub agent: AgentPubKey,
@@ -14,11 +15,45 @@ pub struct Certificate {
     pub certificate_type: CertificateType,
     pub dna_hash: DnaHash,
 }
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum VersionedCertificate {
+    V1(CertificateV1),
+}
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CertificateV1 {
+    pub post_hash: ActionHash,
+    pub agent: AgentPubKey,
+    pub certifications_hashes: Vec<EntryHash>,
+    pub certificate_type: CertificateType,
+    pub dna_hash: DnaHash,
+}
+impl From<VersionedCertificate> for Certificate {
+    fn from(versioned: VersionedCertificate) -> Self {
+        match versioned {
+            VersionedCertificate::V1(certificate) => Certificate {
+                post_hash: certificate.post_hash,
+                agent: certificate.agent,
+                certifications_hashes: certificate.certifications_hashes,
+                certificate_type: certificate.certificate_type,
+                dna_hash: certificate.dna_hash,
+            },
+        }
+    }
+}
 pub fn validate_create_certificate(
     _action: EntryCreationAction,
     certificate: Certificate,
 ) -> ExternResult<ValidateCallbackResult> {
     let record = must_get_valid_record(certificate.post_hash.clone())?;
+    let post_entry_type: EntryType = crate::UnitEntryTypes::Post.try_into()?;
+    if record.action().entry_type() != Some(&post_entry_type) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Certificates can only be issued for Posts"),
+            ),
+        );
+    }
     let _post: crate::Post = record
         .entry()
         .to_app_option()
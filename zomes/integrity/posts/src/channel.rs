@@ -0,0 +1,247 @@
+use hdi::prelude::*;
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub enum PostingPolicy {
+    Anyone,
+    Moderators,
+    Members { members: Vec<AgentPubKey> },
+}
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct Channel {
+    pub name: String,
+    pub description: String,
+    pub posting_policy: PostingPolicy,
+}
+pub const CHANNEL_NAME_MAX_LENGTH: usize = 100;
+pub fn check_channel_fields(channel: &Channel) -> Option<String> {
+    if channel.name.trim().is_empty() {
+        return Some(String::from("Channel name cannot be blank"));
+    }
+    let name_length = channel.name.chars().count();
+    if name_length > CHANNEL_NAME_MAX_LENGTH {
+        return Some(
+            format!(
+                "Channel name is {} characters long, but must be at most {} characters long",
+                name_length, CHANNEL_NAME_MAX_LENGTH
+            ),
+        );
+    }
+    None
+}
+// The creator of a Channel can always post to it, whatever its posting policy. Agents that
+// are moderators through a certificate pass its hash in the ChannelToPosts link tag
+pub fn can_post_to_channel(
+    channel_creator: &AgentPubKey,
+    channel: &Channel,
+    agent: &AgentPubKey,
+    moderator_certificate_hash: Option<ActionHash>,
+) -> ExternResult<bool> {
+    if channel_creator == agent {
+        return Ok(true);
+    }
+    match &channel.posting_policy {
+        PostingPolicy::Anyone => Ok(true),
+        PostingPolicy::Moderators => {
+            let result = crate::validate_moderator(agent, moderator_certificate_hash)?;
+            Ok(matches!(result, ValidateCallbackResult::Valid))
+        }
+        PostingPolicy::Members { members } => Ok(members.contains(agent)),
+    }
+}
+pub fn validate_create_channel(
+    _action: EntryCreationAction,
+    channel: Channel,
+) -> ExternResult<ValidateCallbackResult> {
+    if let Some(error) = check_channel_fields(&channel) {
+        return Ok(ValidateCallbackResult::Invalid(error));
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_update_channel(
+    _action: Update,
+    _channel: Channel,
+    _original_action: EntryCreationAction,
+    _original_channel: Channel,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from("Channels cannot be updated")))
+}
+pub fn validate_delete_channel(
+    _action: Delete,
+    _original_action: EntryCreationAction,
+    _original_channel: Channel,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from("Channels cannot be deleted")))
+}
+pub fn validate_create_link_all_channels(
+    _action: CreateLink,
+    _base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let action_hash = target_address
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("No action hash associated with link".to_string())
+            ),
+        )?;
+    let record = must_get_valid_record(action_hash)?;
+    let channel_entry_type: EntryType = crate::UnitEntryTypes::Channel.try_into()?;
+    if record.action().entry_type() != Some(&channel_entry_type) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("AllChannels links must point to a Channel"),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_delete_link_all_channels(
+    _action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from("AllChannels links cannot be deleted")))
+}
+pub fn validate_create_link_channel_to_posts(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let action_hash = base_address
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("No action hash associated with link".to_string())
+            ),
+        )?;
+    let channel_record = must_get_valid_record(action_hash)?;
+    let channel_entry_type: EntryType = crate::UnitEntryTypes::Channel.try_into()?;
+    if channel_record.action().entry_type() != Some(&channel_entry_type) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("ChannelToPosts links must start from a Channel"),
+            ),
+        );
+    }
+    let channel: crate::Channel = channel_record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("Linked action must reference an entry".to_string())
+            ),
+        )?;
+    let action_hash = target_address
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("No action hash associated with link".to_string())
+            ),
+        )?;
+    let post_record = must_get_valid_record(action_hash)?;
+    let post_entry_type: EntryType = crate::UnitEntryTypes::Post.try_into()?;
+    if post_record.action().entry_type() != Some(&post_entry_type) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("ChannelToPosts links must point to a Post"),
+            ),
+        );
+    }
+    if post_record.action().author() != &action.author {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Only the author of a Post can add it to a Channel"),
+            ),
+        );
+    }
+    let moderator_certificate_hash: Option<ActionHash> = if tag.0.is_empty() {
+        None
+    } else {
+        ExternIO(tag.0).decode().map_err(|e| wasm_error!(e))?
+    };
+    if !can_post_to_channel(
+        channel_record.action().author(),
+        &channel,
+        &action.author,
+        moderator_certificate_hash,
+    )? {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                format!(
+                    "The posting policy of Channel {:?} does not allow this agent to post to it",
+                    channel.name
+                ),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_delete_link_channel_to_posts(
+    action: DeleteLink,
+    original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author != original_action.author {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Only the author of a Post can remove it from a Channel"),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn channel(name: &str, posting_policy: PostingPolicy) -> Channel {
+        Channel {
+            name: name.to_string(),
+            description: String::new(),
+            posting_policy,
+        }
+    }
+    fn agent(byte: u8) -> AgentPubKey {
+        AgentPubKey::from_raw_36(vec![byte; 36])
+    }
+    #[test]
+    fn valid_channel_passes() {
+        assert_eq!(check_channel_fields(&channel("General", PostingPolicy::Anyone)), None);
+    }
+    #[test]
+    fn blank_name_is_rejected() {
+        assert_eq!(
+            check_channel_fields(&channel("  ", PostingPolicy::Anyone)),
+            Some("Channel name cannot be blank".to_string())
+        );
+    }
+    #[test]
+    fn long_name_is_rejected() {
+        assert_eq!(
+            check_channel_fields(&channel(&"a".repeat(101), PostingPolicy::Anyone)),
+            Some(
+                "Channel name is 101 characters long, but must be at most 100 characters long"
+                    .to_string()
+            )
+        );
+    }
+    #[test]
+    fn members_and_creator_can_post() {
+        let members_only = channel(
+            "Members",
+            PostingPolicy::Members {
+                members: vec![agent(1)],
+            },
+        );
+        assert!(can_post_to_channel(&agent(0), &members_only, &agent(0), None).unwrap());
+        assert!(can_post_to_channel(&agent(0), &members_only, &agent(1), None).unwrap());
+        assert!(!can_post_to_channel(&agent(0), &members_only, &agent(2), None).unwrap());
+    }
+}
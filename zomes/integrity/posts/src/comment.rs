@@ -1,14 +1,41 @@
 use hdi::prelude::*;
 #[hdk_entry_helper]
 #[derive(Clone, PartialEq)]
+#[serde(from = "VersionedComment")]
 pub struct Comment {
     pub post_hash: ActionHash,
 }
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum VersionedComment {
+    V1(CommentV1),
+}
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CommentV1 {
+    pub post_hash: ActionHash,
+}
+impl From<VersionedComment> for Comment {
+    fn from(versioned: VersionedComment) -> Self {
+        match versioned {
+            VersionedComment::V1(comment) => Comment {
+                post_hash: comment.post_hash,
+            },
+        }
+    }
+}
 pub fn validate_create_comment(
-    _action: EntryCreationAction,
+    action: EntryCreationAction,
     comment: Comment,
 ) -> ExternResult<ValidateCallbackResult> {
     let record = must_get_valid_record(comment.post_hash.clone())?;
+    let post_entry_type: EntryType = crate::UnitEntryTypes::Post.try_into()?;
+    if record.action().entry_type() != Some(&post_entry_type) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Comments can only be made on Posts"),
+            ),
+        );
+    }
     let _post: crate::Post = record
         .entry()
         .to_app_option()
@@ -18,7 +45,7 @@ pub fn validate_create_comment(
                 WasmErrorInner::Guest(String::from("Dependant action must be accompanied by an entry"))
             ),
         )?;
-    Ok(ValidateCallbackResult::Valid)
+    crate::validate_rate_limit(&action)
 }
 pub fn validate_update_comment(
     _action: Update,
@@ -49,6 +76,14 @@ pub fn validate_create_link_post_to_comments(
             ),
         )?;
     let record = must_get_valid_record(action_hash)?;
+    let post_entry_type: EntryType = crate::UnitEntryTypes::Post.try_into()?;
+    if record.action().entry_type() != Some(&post_entry_type) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("PostToComments links must start from a Post"),
+            ),
+        );
+    }
     let _post: crate::Post = record
         .entry()
         .to_app_option()
@@ -67,6 +102,14 @@ pub fn validate_create_link_post_to_comments(
             ),
         )?;
     let record = must_get_valid_record(action_hash)?;
+    let comment_entry_type: EntryType = crate::UnitEntryTypes::Comment.try_into()?;
+    if record.action().entry_type() != Some(&comment_entry_type) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("PostToComments links must point to a Comment"),
+            ),
+        );
+    }
     let _comment: crate::Comment = record
         .entry()
         .to_app_option()
@@ -1,3 +1,25 @@
+pub mod channel;
+pub use channel::*;
+pub mod migration_pointer;
+pub use migration_pointer::*;
+pub mod rate_limit;
+pub use rate_limit::*;
+pub mod block_list;
+pub use block_list::*;
+pub mod report;
+pub use report::*;
+pub mod moderation_action;
+pub use moderation_action::*;
+pub mod scheduled_post;
+pub use scheduled_post::*;
+pub mod properties;
+pub use properties::*;
+pub mod post_draft;
+pub use post_draft::*;
+pub mod subscription;
+pub use subscription::*;
+pub mod notification;
+pub use notification::*;
 pub mod certificate;
 pub use certificate::*;
 pub mod like;
@@ -16,6 +38,20 @@ pub enum EntryTypes {
     Comment(Comment),
     Like(Like),
     Certificate(Certificate),
+    #[entry_type(visibility = "private")]
+    NotificationReadState(NotificationReadState),
+    #[entry_type(visibility = "private")]
+    Subscription(Subscription),
+    #[entry_type(visibility = "private")]
+    PostDraft(PostDraft),
+    #[entry_type(visibility = "private")]
+    ScheduledPost(ScheduledPost),
+    ModerationAction(ModerationAction),
+    Report(Report),
+    #[entry_type(visibility = "private")]
+    BlockList(BlockList),
+    MigrationPointer(MigrationPointer),
+    Channel(Channel),
 }
 #[derive(Serialize, Deserialize)]
 #[hdk_link_types]
@@ -30,6 +66,15 @@ pub enum LinkTypes {
     PostsByAuthor,
     AllPostsEntryHash,
     PostsByAuthorEntryHash,
+    ModeratedToModerationActions,
+    PostToReports,
+    CommentToReports,
+    ReportedItems,
+    MigrationPointers,
+    MigratedPosts,
+    AllChannels,
+    ChannelToPosts,
+    AgentToLikes,
 }
 #[hdk_extern]
 pub fn genesis_self_check(_data: GenesisSelfCheckData) -> ExternResult<ValidateCallbackResult> {
@@ -58,6 +103,43 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                 EntryTypes::Certificate(certificate) => {
                     validate_create_certificate(EntryCreationAction::Create(action), certificate)
                 }
+                EntryTypes::NotificationReadState(notification_read_state) => {
+                    validate_create_notification_read_state(
+                        EntryCreationAction::Create(action),
+                        notification_read_state,
+                    )
+                }
+                EntryTypes::Subscription(subscription) => {
+                    validate_create_subscription(EntryCreationAction::Create(action), subscription)
+                }
+                EntryTypes::PostDraft(post_draft) => {
+                    validate_create_post_draft(EntryCreationAction::Create(action), post_draft)
+                }
+                EntryTypes::ScheduledPost(scheduled_post) => validate_create_scheduled_post(
+                    EntryCreationAction::Create(action),
+                    scheduled_post,
+                ),
+                EntryTypes::ModerationAction(moderation_action) => {
+                    validate_create_moderation_action(
+                        EntryCreationAction::Create(action),
+                        moderation_action,
+                    )
+                }
+                EntryTypes::Report(report) => {
+                    validate_create_report(EntryCreationAction::Create(action), report)
+                }
+                EntryTypes::BlockList(block_list) => {
+                    validate_create_block_list(EntryCreationAction::Create(action), block_list)
+                }
+                EntryTypes::MigrationPointer(migration_pointer) => {
+                    validate_create_migration_pointer(
+                        EntryCreationAction::Create(action),
+                        migration_pointer,
+                    )
+                }
+                EntryTypes::Channel(channel) => {
+                    validate_create_channel(EntryCreationAction::Create(action), channel)
+                }
             },
             OpEntry::UpdateEntry {
                 app_entry, action, ..
@@ -74,6 +156,43 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                 EntryTypes::Certificate(certificate) => {
                     validate_create_certificate(EntryCreationAction::Update(action), certificate)
                 }
+                EntryTypes::NotificationReadState(notification_read_state) => {
+                    validate_create_notification_read_state(
+                        EntryCreationAction::Update(action),
+                        notification_read_state,
+                    )
+                }
+                EntryTypes::Subscription(subscription) => {
+                    validate_create_subscription(EntryCreationAction::Update(action), subscription)
+                }
+                EntryTypes::PostDraft(post_draft) => {
+                    validate_create_post_draft(EntryCreationAction::Update(action), post_draft)
+                }
+                EntryTypes::ScheduledPost(scheduled_post) => validate_create_scheduled_post(
+                    EntryCreationAction::Update(action),
+                    scheduled_post,
+                ),
+                EntryTypes::ModerationAction(moderation_action) => {
+                    validate_create_moderation_action(
+                        EntryCreationAction::Update(action),
+                        moderation_action,
+                    )
+                }
+                EntryTypes::Report(report) => {
+                    validate_create_report(EntryCreationAction::Update(action), report)
+                }
+                EntryTypes::BlockList(block_list) => {
+                    validate_create_block_list(EntryCreationAction::Update(action), block_list)
+                }
+                EntryTypes::MigrationPointer(migration_pointer) => {
+                    validate_create_migration_pointer(
+                        EntryCreationAction::Update(action),
+                        migration_pointer,
+                    )
+                }
+                EntryTypes::Channel(channel) => {
+                    validate_create_channel(EntryCreationAction::Update(action), channel)
+                }
             },
             _ => Ok(ValidateCallbackResult::Valid),
         },
@@ -125,6 +244,38 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                 target_address,
                 tag,
             ),
+            LinkTypes::ModeratedToModerationActions => {
+                validate_create_link_moderated_to_moderation_actions(
+                    action,
+                    base_address,
+                    target_address,
+                    tag,
+                )
+            }
+            LinkTypes::PostToReports => {
+                validate_create_link_post_to_reports(action, base_address, target_address, tag)
+            }
+            LinkTypes::CommentToReports => {
+                validate_create_link_comment_to_reports(action, base_address, target_address, tag)
+            }
+            LinkTypes::ReportedItems => {
+                validate_create_link_reported_items(action, base_address, target_address, tag)
+            }
+            LinkTypes::MigrationPointers => {
+                validate_create_link_migration_pointers(action, base_address, target_address, tag)
+            }
+            LinkTypes::MigratedPosts => {
+                validate_create_link_migrated_posts(action, base_address, target_address, tag)
+            }
+            LinkTypes::AllChannels => {
+                validate_create_link_all_channels(action, base_address, target_address, tag)
+            }
+            LinkTypes::ChannelToPosts => {
+                validate_create_link_channel_to_posts(action, base_address, target_address, tag)
+            }
+            LinkTypes::AgentToLikes => {
+                validate_create_link_agent_to_likes(action, base_address, target_address, tag)
+            }
         },
         FlatOp::RegisterDeleteLink {
             link_type,
@@ -206,6 +357,71 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                 target_address,
                 tag,
             ),
+            LinkTypes::ModeratedToModerationActions => {
+                validate_delete_link_moderated_to_moderation_actions(
+                    action,
+                    original_action,
+                    base_address,
+                    target_address,
+                    tag,
+                )
+            }
+            LinkTypes::PostToReports => validate_delete_link_post_to_reports(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+            ),
+            LinkTypes::CommentToReports => validate_delete_link_comment_to_reports(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+            ),
+            LinkTypes::ReportedItems => validate_delete_link_reported_items(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+            ),
+            LinkTypes::MigrationPointers => validate_delete_link_migration_pointers(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+            ),
+            LinkTypes::MigratedPosts => validate_delete_link_migrated_posts(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+            ),
+            LinkTypes::AllChannels => validate_delete_link_all_channels(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+            ),
+            LinkTypes::ChannelToPosts => validate_delete_link_channel_to_posts(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+            ),
+            LinkTypes::AgentToLikes => validate_delete_link_agent_to_likes(
+                action,
+                original_action,
+                base_address,
+                target_address,
+                tag,
+            ),
         },
         FlatOp::StoreRecord(store_record) => match store_record {
             OpRecord::CreateEntry { app_entry, action } => match app_entry {
@@ -221,6 +437,43 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                 EntryTypes::Certificate(certificate) => {
                     validate_create_certificate(EntryCreationAction::Create(action), certificate)
                 }
+                EntryTypes::NotificationReadState(notification_read_state) => {
+                    validate_create_notification_read_state(
+                        EntryCreationAction::Create(action),
+                        notification_read_state,
+                    )
+                }
+                EntryTypes::Subscription(subscription) => {
+                    validate_create_subscription(EntryCreationAction::Create(action), subscription)
+                }
+                EntryTypes::PostDraft(post_draft) => {
+                    validate_create_post_draft(EntryCreationAction::Create(action), post_draft)
+                }
+                EntryTypes::ScheduledPost(scheduled_post) => validate_create_scheduled_post(
+                    EntryCreationAction::Create(action),
+                    scheduled_post,
+                ),
+                EntryTypes::ModerationAction(moderation_action) => {
+                    validate_create_moderation_action(
+                        EntryCreationAction::Create(action),
+                        moderation_action,
+                    )
+                }
+                EntryTypes::Report(report) => {
+                    validate_create_report(EntryCreationAction::Create(action), report)
+                }
+                EntryTypes::BlockList(block_list) => {
+                    validate_create_block_list(EntryCreationAction::Create(action), block_list)
+                }
+                EntryTypes::MigrationPointer(migration_pointer) => {
+                    validate_create_migration_pointer(
+                        EntryCreationAction::Create(action),
+                        migration_pointer,
+                    )
+                }
+                EntryTypes::Channel(channel) => {
+                    validate_create_channel(EntryCreationAction::Create(action), channel)
+                }
             },
             OpRecord::UpdateEntry {
                 original_action_hash,
@@ -355,6 +608,284 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                             Ok(result)
                         }
                     }
+                    EntryTypes::NotificationReadState(notification_read_state) => {
+                        let result = validate_create_notification_read_state(
+                            EntryCreationAction::Update(action.clone()),
+                            notification_read_state.clone(),
+                        )?;
+                        if let ValidateCallbackResult::Valid = result {
+                            let original_notification_read_state: Option<NotificationReadState> =
+                                original_record
+                                    .entry()
+                                    .to_app_option()
+                                    .map_err(|e| wasm_error!(e))?;
+                            let original_notification_read_state =
+                                match original_notification_read_state {
+                                    Some(notification_read_state) => notification_read_state,
+                                    None => {
+                                        return Ok(
+                                            ValidateCallbackResult::Invalid(
+                                                "The updated entry type must be the same as the original entry type"
+                                                    .to_string(),
+                                            ),
+                                        );
+                                    }
+                                };
+                            validate_update_notification_read_state(
+                                action,
+                                notification_read_state,
+                                original_action,
+                                original_notification_read_state,
+                            )
+                        } else {
+                            Ok(result)
+                        }
+                    }
+                    EntryTypes::Subscription(subscription) => {
+                        let result = validate_create_subscription(
+                            EntryCreationAction::Update(action.clone()),
+                            subscription.clone(),
+                        )?;
+                        if let ValidateCallbackResult::Valid = result {
+                            let original_subscription: Option<Subscription> = original_record
+                                .entry()
+                                .to_app_option()
+                                .map_err(|e| wasm_error!(e))?;
+                            let original_subscription = match original_subscription {
+                                Some(subscription) => subscription,
+                                None => {
+                                    return Ok(
+                                            ValidateCallbackResult::Invalid(
+                                                "The updated entry type must be the same as the original entry type"
+                                                    .to_string(),
+                                            ),
+                                        );
+                                }
+                            };
+                            validate_update_subscription(
+                                action,
+                                subscription,
+                                original_action,
+                                original_subscription,
+                            )
+                        } else {
+                            Ok(result)
+                        }
+                    }
+                    EntryTypes::PostDraft(post_draft) => {
+                        let result = validate_create_post_draft(
+                            EntryCreationAction::Update(action.clone()),
+                            post_draft.clone(),
+                        )?;
+                        if let ValidateCallbackResult::Valid = result {
+                            let original_post_draft: Option<PostDraft> = original_record
+                                .entry()
+                                .to_app_option()
+                                .map_err(|e| wasm_error!(e))?;
+                            let original_post_draft = match original_post_draft {
+                                Some(post_draft) => post_draft,
+                                None => {
+                                    return Ok(
+                                            ValidateCallbackResult::Invalid(
+                                                "The updated entry type must be the same as the original entry type"
+                                                    .to_string(),
+                                            ),
+                                        );
+                                }
+                            };
+                            validate_update_post_draft(
+                                action,
+                                post_draft,
+                                original_action,
+                                original_post_draft,
+                            )
+                        } else {
+                            Ok(result)
+                        }
+                    }
+                    EntryTypes::ScheduledPost(scheduled_post) => {
+                        let result = validate_create_scheduled_post(
+                            EntryCreationAction::Update(action.clone()),
+                            scheduled_post.clone(),
+                        )?;
+                        if let ValidateCallbackResult::Valid = result {
+                            let original_scheduled_post: Option<ScheduledPost> = original_record
+                                .entry()
+                                .to_app_option()
+                                .map_err(|e| wasm_error!(e))?;
+                            let original_scheduled_post = match original_scheduled_post {
+                                Some(scheduled_post) => scheduled_post,
+                                None => {
+                                    return Ok(
+                                            ValidateCallbackResult::Invalid(
+                                                "The updated entry type must be the same as the original entry type"
+                                                    .to_string(),
+                                            ),
+                                        );
+                                }
+                            };
+                            validate_update_scheduled_post(
+                                action,
+                                scheduled_post,
+                                original_action,
+                                original_scheduled_post,
+                            )
+                        } else {
+                            Ok(result)
+                        }
+                    }
+                    EntryTypes::ModerationAction(moderation_action) => {
+                        let result = validate_create_moderation_action(
+                            EntryCreationAction::Update(action.clone()),
+                            moderation_action.clone(),
+                        )?;
+                        if let ValidateCallbackResult::Valid = result {
+                            let original_moderation_action: Option<ModerationAction> =
+                                original_record
+                                    .entry()
+                                    .to_app_option()
+                                    .map_err(|e| wasm_error!(e))?;
+                            let original_moderation_action = match original_moderation_action {
+                                Some(moderation_action) => moderation_action,
+                                None => {
+                                    return Ok(
+                                            ValidateCallbackResult::Invalid(
+                                                "The updated entry type must be the same as the original entry type"
+                                                    .to_string(),
+                                            ),
+                                        );
+                                }
+                            };
+                            validate_update_moderation_action(
+                                action,
+                                moderation_action,
+                                original_action,
+                                original_moderation_action,
+                            )
+                        } else {
+                            Ok(result)
+                        }
+                    }
+                    EntryTypes::Report(report) => {
+                        let result = validate_create_report(
+                            EntryCreationAction::Update(action.clone()),
+                            report.clone(),
+                        )?;
+                        if let ValidateCallbackResult::Valid = result {
+                            let original_report: Option<Report> = original_record
+                                .entry()
+                                .to_app_option()
+                                .map_err(|e| wasm_error!(e))?;
+                            let original_report = match original_report {
+                                Some(report) => report,
+                                None => {
+                                    return Ok(
+                                            ValidateCallbackResult::Invalid(
+                                                "The updated entry type must be the same as the original entry type"
+                                                    .to_string(),
+                                            ),
+                                        );
+                                }
+                            };
+                            validate_update_report(action, report, original_action, original_report)
+                        } else {
+                            Ok(result)
+                        }
+                    }
+                    EntryTypes::BlockList(block_list) => {
+                        let result = validate_create_block_list(
+                            EntryCreationAction::Update(action.clone()),
+                            block_list.clone(),
+                        )?;
+                        if let ValidateCallbackResult::Valid = result {
+                            let original_block_list: Option<BlockList> = original_record
+                                .entry()
+                                .to_app_option()
+                                .map_err(|e| wasm_error!(e))?;
+                            let original_block_list = match original_block_list {
+                                Some(block_list) => block_list,
+                                None => {
+                                    return Ok(
+                                            ValidateCallbackResult::Invalid(
+                                                "The updated entry type must be the same as the original entry type"
+                                                    .to_string(),
+                                            ),
+                                        );
+                                }
+                            };
+                            validate_update_block_list(
+                                action,
+                                block_list,
+                                original_action,
+                                original_block_list,
+                            )
+                        } else {
+                            Ok(result)
+                        }
+                    }
+                    EntryTypes::MigrationPointer(migration_pointer) => {
+                        let result = validate_create_migration_pointer(
+                            EntryCreationAction::Update(action.clone()),
+                            migration_pointer.clone(),
+                        )?;
+                        if let ValidateCallbackResult::Valid = result {
+                            let original_migration_pointer: Option<MigrationPointer> =
+                                original_record
+                                    .entry()
+                                    .to_app_option()
+                                    .map_err(|e| wasm_error!(e))?;
+                            let original_migration_pointer = match original_migration_pointer {
+                                Some(migration_pointer) => migration_pointer,
+                                None => {
+                                    return Ok(
+                                            ValidateCallbackResult::Invalid(
+                                                "The updated entry type must be the same as the original entry type"
+                                                    .to_string(),
+                                            ),
+                                        );
+                                }
+                            };
+                            validate_update_migration_pointer(
+                                action,
+                                migration_pointer,
+                                original_action,
+                                original_migration_pointer,
+                            )
+                        } else {
+                            Ok(result)
+                        }
+                    }
+                    EntryTypes::Channel(channel) => {
+                        let result = validate_create_channel(
+                            EntryCreationAction::Update(action.clone()),
+                            channel.clone(),
+                        )?;
+                        if let ValidateCallbackResult::Valid = result {
+                            let original_channel: Option<Channel> = original_record
+                                .entry()
+                                .to_app_option()
+                                .map_err(|e| wasm_error!(e))?;
+                            let original_channel = match original_channel {
+                                Some(channel) => channel,
+                                None => {
+                                    return Ok(
+                                            ValidateCallbackResult::Invalid(
+                                                "The updated entry type must be the same as the original entry type"
+                                                    .to_string(),
+                                            ),
+                                        );
+                                }
+                            };
+                            validate_update_channel(
+                                action,
+                                channel,
+                                original_action,
+                                original_channel,
+                            )
+                        } else {
+                            Ok(result)
+                        }
+                    }
                 }
             }
             OpRecord::DeleteEntry {
@@ -423,6 +954,49 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                     EntryTypes::Certificate(original_certificate) => {
                         validate_delete_certificate(action, original_action, original_certificate)
                     }
+                    EntryTypes::NotificationReadState(original_notification_read_state) => {
+                        validate_delete_notification_read_state(
+                            action,
+                            original_action,
+                            original_notification_read_state,
+                        )
+                    }
+                    EntryTypes::Subscription(original_subscription) => {
+                        validate_delete_subscription(action, original_action, original_subscription)
+                    }
+                    EntryTypes::PostDraft(original_post_draft) => {
+                        validate_delete_post_draft(action, original_action, original_post_draft)
+                    }
+                    EntryTypes::ScheduledPost(original_scheduled_post) => {
+                        validate_delete_scheduled_post(
+                            action,
+                            original_action,
+                            original_scheduled_post,
+                        )
+                    }
+                    EntryTypes::ModerationAction(original_moderation_action) => {
+                        validate_delete_moderation_action(
+                            action,
+                            original_action,
+                            original_moderation_action,
+                        )
+                    }
+                    EntryTypes::Report(original_report) => {
+                        validate_delete_report(action, original_action, original_report)
+                    }
+                    EntryTypes::BlockList(original_block_list) => {
+                        validate_delete_block_list(action, original_action, original_block_list)
+                    }
+                    EntryTypes::MigrationPointer(original_migration_pointer) => {
+                        validate_delete_migration_pointer(
+                            action,
+                            original_action,
+                            original_migration_pointer,
+                        )
+                    }
+                    EntryTypes::Channel(original_channel) => {
+                        validate_delete_channel(action, original_action, original_channel)
+                    }
                 }
             }
             OpRecord::CreateLink {
@@ -483,6 +1057,44 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                         tag,
                     )
                 }
+                LinkTypes::ModeratedToModerationActions => {
+                    validate_create_link_moderated_to_moderation_actions(
+                        action,
+                        base_address,
+                        target_address,
+                        tag,
+                    )
+                }
+                LinkTypes::PostToReports => {
+                    validate_create_link_post_to_reports(action, base_address, target_address, tag)
+                }
+                LinkTypes::CommentToReports => validate_create_link_comment_to_reports(
+                    action,
+                    base_address,
+                    target_address,
+                    tag,
+                ),
+                LinkTypes::ReportedItems => {
+                    validate_create_link_reported_items(action, base_address, target_address, tag)
+                }
+                LinkTypes::MigrationPointers => validate_create_link_migration_pointers(
+                    action,
+                    base_address,
+                    target_address,
+                    tag,
+                ),
+                LinkTypes::MigratedPosts => {
+                    validate_create_link_migrated_posts(action, base_address, target_address, tag)
+                }
+                LinkTypes::AllChannels => {
+                    validate_create_link_all_channels(action, base_address, target_address, tag)
+                }
+                LinkTypes::ChannelToPosts => {
+                    validate_create_link_channel_to_posts(action, base_address, target_address, tag)
+                }
+                LinkTypes::AgentToLikes => {
+                    validate_create_link_agent_to_likes(action, base_address, target_address, tag)
+                }
             },
             OpRecord::DeleteLink {
                 original_action_hash,
@@ -582,6 +1194,71 @@ pub fn validate(op: Op) -> ExternResult<ValidateCallbackResult> {
                             create_link.tag,
                         )
                     }
+                    LinkTypes::ModeratedToModerationActions => {
+                        validate_delete_link_moderated_to_moderation_actions(
+                            action,
+                            create_link.clone(),
+                            base_address,
+                            create_link.target_address,
+                            create_link.tag,
+                        )
+                    }
+                    LinkTypes::PostToReports => validate_delete_link_post_to_reports(
+                        action,
+                        create_link.clone(),
+                        base_address,
+                        create_link.target_address,
+                        create_link.tag,
+                    ),
+                    LinkTypes::CommentToReports => validate_delete_link_comment_to_reports(
+                        action,
+                        create_link.clone(),
+                        base_address,
+                        create_link.target_address,
+                        create_link.tag,
+                    ),
+                    LinkTypes::ReportedItems => validate_delete_link_reported_items(
+                        action,
+                        create_link.clone(),
+                        base_address,
+                        create_link.target_address,
+                        create_link.tag,
+                    ),
+                    LinkTypes::MigrationPointers => validate_delete_link_migration_pointers(
+                        action,
+                        create_link.clone(),
+                        base_address,
+                        create_link.target_address,
+                        create_link.tag,
+                    ),
+                    LinkTypes::MigratedPosts => validate_delete_link_migrated_posts(
+                        action,
+                        create_link.clone(),
+                        base_address,
+                        create_link.target_address,
+                        create_link.tag,
+                    ),
+                    LinkTypes::AllChannels => validate_delete_link_all_channels(
+                        action,
+                        create_link.clone(),
+                        base_address,
+                        create_link.target_address,
+                        create_link.tag,
+                    ),
+                    LinkTypes::ChannelToPosts => validate_delete_link_channel_to_posts(
+                        action,
+                        create_link.clone(),
+                        base_address,
+                        create_link.target_address,
+                        create_link.tag,
+                    ),
+                    LinkTypes::AgentToLikes => validate_delete_link_agent_to_likes(
+                        action,
+                        create_link.clone(),
+                        base_address,
+                        create_link.target_address,
+                        create_link.tag,
+                    ),
                 }
             }
             OpRecord::CreatePrivateEntry { .. } => Ok(ValidateCallbackResult::Valid),
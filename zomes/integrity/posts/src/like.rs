@@ -1,16 +1,45 @@
 use hdi::prelude::*;
 #[hdk_entry_helper]
 #[derive(Clone, PartialEq)]
+#[serde(from = "VersionedLike")]
 pub struct Like {
     pub like_hash: Option<ActionHash>,
     pub agent: AgentPubKey,
 }
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum VersionedLike {
+    V1(LikeV1),
+}
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LikeV1 {
+    pub like_hash: Option<ActionHash>,
+    pub agent: AgentPubKey,
+}
+impl From<VersionedLike> for Like {
+    fn from(versioned: VersionedLike) -> Self {
+        match versioned {
+            VersionedLike::V1(like) => Like {
+                like_hash: like.like_hash,
+                agent: like.agent,
+            },
+        }
+    }
+}
 pub fn validate_create_like(
     _action: EntryCreationAction,
     like: Like,
 ) -> ExternResult<ValidateCallbackResult> {
     if let Some(action_hash) = like.like_hash.clone() {
         let record = must_get_valid_record(action_hash)?;
+        let like_entry_type: EntryType = crate::UnitEntryTypes::Like.try_into()?;
+        if record.action().entry_type() != Some(&like_entry_type) {
+            return Ok(
+                ValidateCallbackResult::Invalid(
+                    String::from("Likes can only reference other Likes"),
+                ),
+            );
+        }
         let _like: crate::Like = record
             .entry()
             .to_app_option()
@@ -91,3 +120,73 @@ pub fn validate_delete_link_like_to_likes(
 ) -> ExternResult<ValidateCallbackResult> {
     Ok(ValidateCallbackResult::Valid)
 }
+pub fn validate_create_link_agent_to_likes(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let agent = base_address
+        .into_agent_pub_key()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("No agent pub key associated with link".to_string())
+            ),
+        )?;
+    let action_hash = target_address
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("No action hash associated with link".to_string())
+            ),
+        )?;
+    let record = must_get_valid_record(action_hash)?;
+    let like_entry_type: EntryType = crate::UnitEntryTypes::Like.try_into()?;
+    if record.action().entry_type() != Some(&like_entry_type) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("AgentToLikes links must point to a Like"),
+            ),
+        );
+    }
+    let like: crate::Like = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("Linked action must reference an entry".to_string())
+            ),
+        )?;
+    if like.agent.ne(&agent) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("AgentToLikes links must start from the liked agent"),
+            ),
+        );
+    }
+    if record.action().author().ne(&action.author) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Only the author of a Like can link it to the liked agent"),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_delete_link_agent_to_likes(
+    action: DeleteLink,
+    original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    if action.author.ne(&original_action.author) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Only the author of a Like can delete its AgentToLikes link"),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
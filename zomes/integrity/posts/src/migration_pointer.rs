@@ -0,0 +1,178 @@
+use hdi::prelude::*;
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct MigrationPointer {
+    pub previous_dna_hash: DnaHash,
+}
+pub fn validate_create_migration_pointer(
+    _action: EntryCreationAction,
+    migration_pointer: MigrationPointer,
+) -> ExternResult<ValidateCallbackResult> {
+    if crate::posts_properties()?.previous_dna_hash != Some(migration_pointer.previous_dna_hash)
+    {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from(
+                    "MigrationPointer must point to the previous DNA declared in the DNA properties",
+                ),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_update_migration_pointer(
+    _action: Update,
+    _migration_pointer: MigrationPointer,
+    _original_action: EntryCreationAction,
+    _original_migration_pointer: MigrationPointer,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from("MigrationPointers cannot be updated")))
+}
+pub fn validate_delete_migration_pointer(
+    _action: Delete,
+    _original_action: EntryCreationAction,
+    _original_migration_pointer: MigrationPointer,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from("MigrationPointers cannot be deleted")))
+}
+pub fn validate_create_link_migration_pointers(
+    _action: CreateLink,
+    _base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let action_hash = target_address
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("No action hash associated with link".to_string())
+            ),
+        )?;
+    let record = must_get_valid_record(action_hash)?;
+    let _migration_pointer: crate::MigrationPointer = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("Linked action must reference an entry".to_string())
+            ),
+        )?;
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_delete_link_migration_pointers(
+    _action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(
+        ValidateCallbackResult::Invalid(
+            String::from("MigrationPointers links cannot be deleted"),
+        ),
+    )
+}
+// The base is the hash of the Post in the previous DNA, which can't be fetched from this one:
+// the tag carries the signed action of the copied revision instead, which proves what the
+// author published there. Its entry can't be fetched either, so the copy is compared with it by
+// hashing the copy in each shape that the previous DNA could have committed it in
+pub fn validate_create_link_migrated_posts(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let previous_post_hash = base_address
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("No action hash associated with link".to_string())
+            ),
+        )?;
+    let action_hash = target_address
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("No action hash associated with link".to_string())
+            ),
+        )?;
+    let record = must_get_valid_record(action_hash)?;
+    let post_entry_type: EntryType = crate::UnitEntryTypes::Post.try_into()?;
+    if record.action().entry_type() != Some(&post_entry_type) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("MigratedPosts links must point to a Post"),
+            ),
+        );
+    }
+    let previous_action: SignedActionHashed = ExternIO(tag.0)
+        .decode()
+        .map_err(|e| wasm_error!(e))?;
+    if record.action().author().ne(&action.author)
+        || previous_action.action().author().ne(&action.author)
+    {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Only the author of a Post can migrate it"),
+            ),
+        );
+    }
+    let revises_previous_post = match previous_action.action() {
+        Action::Create(_) => previous_action.action_address().eq(&previous_post_hash),
+        Action::Update(update) => update.original_action_address.eq(&previous_post_hash),
+        _ => false,
+    };
+    if !revises_previous_post
+        || hash_action(previous_action.action().clone())?.ne(previous_action.action_address())
+        || !verify_signature(
+            action.author.clone(),
+            previous_action.signature().clone(),
+            previous_action.action().clone(),
+        )?
+    {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from(
+                    "MigratedPosts links must carry the signed action of the previous Post",
+                ),
+            ),
+        );
+    }
+    let migrated_post: crate::Post = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("Linked action must reference an entry".to_string())
+            ),
+        )?;
+    let mut is_same_post = false;
+    for entry in crate::post_entries_in_every_shape(&migrated_post)? {
+        if previous_action.action().entry_hash() == Some(&hash_entry(entry)?) {
+            is_same_post = true;
+        }
+    }
+    if !is_same_post {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("A migrated Post must have the same content as the previous Post"),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_delete_link_migrated_posts(
+    _action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(
+        ValidateCallbackResult::Invalid(
+            String::from("MigratedPosts links cannot be deleted"),
+        ),
+    )
+}
@@ -0,0 +1,158 @@
+use hdi::prelude::*;
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub enum ModerationDecision {
+    Hide,
+    Unhide,
+}
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct ModerationAction {
+    pub target_hash: ActionHash,
+    pub decision: ModerationDecision,
+    pub reason: String,
+    pub moderator_certificate_hash: Option<ActionHash>,
+}
+pub fn validate_moderator(
+    agent: &AgentPubKey,
+    moderator_certificate_hash: Option<ActionHash>,
+) -> ExternResult<ValidateCallbackResult> {
+    let properties = crate::posts_properties()?;
+    if properties.moderators.contains(agent) {
+        return Ok(ValidateCallbackResult::Valid);
+    }
+    let (Some(certificate_hash), Some(certificate_type)) = (
+        moderator_certificate_hash,
+        properties.moderator_certificate_type,
+    ) else {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Only moderators can create ModerationActions"),
+            ),
+        );
+    };
+    let record = must_get_valid_record(certificate_hash)?;
+    let certificate: crate::Certificate = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest(String::from("Dependant action must be accompanied by an entry"))
+            ),
+        )?;
+    if certificate.agent.ne(agent) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("The moderator certificate was not issued to the moderator"),
+            ),
+        );
+    }
+    if certificate.certificate_type.ne(&certificate_type) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("The moderator certificate is not of the moderator certificate type"),
+            ),
+        );
+    }
+    if !properties.moderators.contains(record.action().author()) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("The moderator certificate was not issued by a moderator"),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_create_moderation_action(
+    action: EntryCreationAction,
+    moderation_action: ModerationAction,
+) -> ExternResult<ValidateCallbackResult> {
+    let record = must_get_valid_record(moderation_action.target_hash.clone())?;
+    let post_entry_type: EntryType = crate::UnitEntryTypes::Post.try_into()?;
+    let comment_entry_type: EntryType = crate::UnitEntryTypes::Comment.try_into()?;
+    let entry_type = record.action().entry_type();
+    if entry_type != Some(&post_entry_type) && entry_type != Some(&comment_entry_type) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Only posts and comments can be moderated"),
+            ),
+        );
+    }
+    validate_moderator(action.author(), moderation_action.moderator_certificate_hash)
+}
+pub fn validate_update_moderation_action(
+    _action: Update,
+    _moderation_action: ModerationAction,
+    _original_action: EntryCreationAction,
+    _original_moderation_action: ModerationAction,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from("ModerationActions cannot be updated")))
+}
+pub fn validate_delete_moderation_action(
+    _action: Delete,
+    _original_action: EntryCreationAction,
+    _original_moderation_action: ModerationAction,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from("ModerationActions cannot be deleted")))
+}
+pub fn validate_create_link_moderated_to_moderation_actions(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let moderated_hash = base_address
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("No action hash associated with link".to_string())
+            ),
+        )?;
+    let action_hash = target_address
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("No action hash associated with link".to_string())
+            ),
+        )?;
+    let record = must_get_valid_record(action_hash)?;
+    let moderation_action: crate::ModerationAction = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("Linked action must reference an entry"
+                .to_string())
+            ),
+        )?;
+    if moderation_action.target_hash.ne(&moderated_hash) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("ModerationActions must be linked from the item they moderate"),
+            ),
+        );
+    }
+    if record.action().author().ne(&action.author) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Only the moderator can link their ModerationActions"),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_delete_link_moderated_to_moderation_actions(
+    _action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(
+        ValidateCallbackResult::Invalid(
+            String::from("ModeratedToModerationActions links cannot be deleted"),
+        ),
+    )
+}
@@ -0,0 +1,57 @@
+use hdi::prelude::*;
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub enum NotificationEvent {
+    CommentCreated { post_hash: ActionHash, comment_hash: ActionHash },
+    LikeCreated { like_hash: ActionHash },
+    CertificateCreated { post_hash: ActionHash, certificate_hash: ActionHash },
+}
+impl NotificationEvent {
+    pub fn action_hash(&self) -> &ActionHash {
+        match self {
+            NotificationEvent::CommentCreated { comment_hash, .. } => comment_hash,
+            NotificationEvent::LikeCreated { like_hash } => like_hash,
+            NotificationEvent::CertificateCreated { certificate_hash, .. } => {
+                certificate_hash
+            }
+        }
+    }
+}
+// Which notifications the agent has read. Notifications themselves are derived from the links
+// to the agent's content on the DHT, so only this marker needs to be kept, in the private chain:
+// every notification up to `read_until` is read, and so are those in `read_hashes`
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct NotificationReadState {
+    pub read_until: Timestamp,
+    pub read_hashes: Vec<ActionHash>,
+}
+impl Default for NotificationReadState {
+    fn default() -> Self {
+        Self {
+            read_until: Timestamp::from_micros(0),
+            read_hashes: vec![],
+        }
+    }
+}
+pub fn validate_create_notification_read_state(
+    _action: EntryCreationAction,
+    _notification_read_state: NotificationReadState,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_update_notification_read_state(
+    _action: Update,
+    _notification_read_state: NotificationReadState,
+    _original_action: EntryCreationAction,
+    _original_notification_read_state: NotificationReadState,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_delete_notification_read_state(
+    _action: Delete,
+    _original_action: EntryCreationAction,
+    _original_notification_read_state: NotificationReadState,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Valid)
+}
@@ -1,15 +1,216 @@
 use hdi::prelude::*;
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+#[serde(tag = "type")]
+pub enum ContentFormat {
+    #[default]
+    Plain,
+    Markdown,
+}
 #[hdk_entry_helper]
 #[derive(Clone, PartialEq)]
+#[serde(from = "VersionedPost")]
 pub struct Post {
     pub title: String,
     pub needs: Vec<String>,
+    pub body: String,
+    pub format: ContentFormat,
+    pub attachments: Vec<ActionHash>,
+}
+// Every shape that Post entries have been committed with, newest first. Entries are committed
+// in the newest shape, and older ones are upcast when they are read
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(untagged)]
+pub enum VersionedPost {
+    V2(PostV2),
+    V1(PostV1),
+}
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PostV2 {
+    pub title: String,
+    pub needs: Vec<String>,
+    pub body: String,
+    pub format: ContentFormat,
+    pub attachments: Vec<ActionHash>,
+}
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PostV1 {
+    pub title: String,
+    pub needs: Vec<String>,
+}
+impl From<VersionedPost> for Post {
+    fn from(versioned: VersionedPost) -> Self {
+        match versioned {
+            VersionedPost::V2(post) => Post {
+                title: post.title,
+                needs: post.needs,
+                body: post.body,
+                format: post.format,
+                attachments: post.attachments,
+            },
+            VersionedPost::V1(post) => Post {
+                title: post.title,
+                needs: post.needs,
+                body: String::new(),
+                format: ContentFormat::Plain,
+                attachments: vec![],
+            },
+        }
+    }
+}
+// The entries that a Post could have been committed as, one per shape of VersionedPost that can
+// hold it. Older shapes only hold Posts whose newer fields are at the defaults they upcast to
+pub fn post_entries_in_every_shape(post: &Post) -> ExternResult<Vec<Entry>> {
+    let mut shapes = vec![
+        ExternIO::encode(PostV2 {
+            title: post.title.clone(),
+            needs: post.needs.clone(),
+            body: post.body.clone(),
+            format: post.format.clone(),
+            attachments: post.attachments.clone(),
+        }),
+    ];
+    if post.body.is_empty() && post.format == ContentFormat::Plain
+        && post.attachments.is_empty()
+    {
+        shapes
+            .push(
+                ExternIO::encode(PostV1 {
+                    title: post.title.clone(),
+                    needs: post.needs.clone(),
+                }),
+            );
+    }
+    shapes
+        .into_iter()
+        .map(|shape| {
+            let bytes = shape.map_err(|e| wasm_error!(e))?.0;
+            Ok(Entry::App(AppEntryBytes(SerializedBytes::from(UnsafeBytes::from(bytes)))))
+        })
+        .collect()
+}
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct PostLimits {
+    pub title_min_length: usize,
+    pub title_max_length: usize,
+    pub max_needs: usize,
+    pub need_max_length: usize,
+    pub allowed_characters: Option<String>,
+    pub body_max_bytes: usize,
+    pub max_attachments: usize,
+}
+impl Default for PostLimits {
+    fn default() -> Self {
+        Self {
+            title_min_length: 1,
+            title_max_length: 200,
+            max_needs: 20,
+            need_max_length: 200,
+            allowed_characters: None,
+            body_max_bytes: 10_000,
+            max_attachments: 10,
+        }
+    }
+}
+fn check_characters(field: &str, value: &str, limits: &PostLimits) -> Option<String> {
+    let disallowed = value
+        .chars()
+        .find(|c| {
+            c.is_control()
+                || limits
+                    .allowed_characters
+                    .as_ref()
+                    .is_some_and(|allowed| !allowed.contains(*c))
+        })?;
+    Some(format!("{} contains the disallowed character {:?}", field, disallowed))
+}
+pub fn check_post_fields(post: &Post, limits: &PostLimits) -> Option<String> {
+    let title_length = post.title.chars().count();
+    if title_length < limits.title_min_length || title_length > limits.title_max_length {
+        return Some(
+            format!(
+                "Post title is {} characters long, but must be between {} and {} characters long",
+                title_length, limits.title_min_length, limits.title_max_length
+            ),
+        );
+    }
+    if let Some(error) = check_characters("Post title", &post.title, limits) {
+        return Some(error);
+    }
+    if post.needs.len() > limits.max_needs {
+        return Some(
+            format!(
+                "Post has {} needs, but at most {} are allowed",
+                post.needs.len(), limits.max_needs
+            ),
+        );
+    }
+    for (index, need) in post.needs.iter().enumerate() {
+        let need_length = need.chars().count();
+        if need_length > limits.need_max_length {
+            return Some(
+                format!(
+                    "Post need {} is {} characters long, but at most {} are allowed",
+                    index, need_length, limits.need_max_length
+                ),
+            );
+        }
+        if let Some(error) = check_characters(&format!("Post need {}", index), need, limits) {
+            return Some(error);
+        }
+    }
+    if post.body.len() > limits.body_max_bytes {
+        return Some(
+            format!(
+                "Post body is {} bytes long, but at most {} are allowed",
+                post.body.len(), limits.body_max_bytes
+            ),
+        );
+    }
+    if post.attachments.len() > limits.max_attachments {
+        return Some(
+            format!(
+                "Post has {} attachments, but at most {} are allowed",
+                post.attachments.len(), limits.max_attachments
+            ),
+        );
+    }
+    None
+}
+// FileManifests are committed by the file_storage_integrity zome, so they are recognised by the
+// index of that zome in the DNA and by their index among its entry types
+fn is_file_manifest(entry_type: Option<&EntryType>) -> ExternResult<bool> {
+    let Some(EntryType::App(app_entry_def)) = entry_type else {
+        return Ok(false);
+    };
+    let file_storage_zome_index = dna_info()?
+        .zome_names
+        .iter()
+        .position(|zome_name| zome_name.0 == file_storage_types::FILE_STORAGE_INTEGRITY_ZOME_NAME);
+    Ok(
+        file_storage_zome_index == Some(app_entry_def.zome_index.0 as usize)
+            && app_entry_def.entry_index.0 == file_storage_types::FILE_MANIFEST_ENTRY_INDEX,
+    )
 }
 pub fn validate_create_post(
-    _action: EntryCreationAction,
-    _post: Post,
+    action: EntryCreationAction,
+    post: Post,
 ) -> ExternResult<ValidateCallbackResult> {
-    Ok(ValidateCallbackResult::Valid)
+    let limits = crate::posts_properties()?.post_limits;
+    if let Some(error) = check_post_fields(&post, &limits) {
+        return Ok(ValidateCallbackResult::Invalid(error));
+    }
+    for attachment in post.attachments {
+        let record = must_get_valid_record(attachment)?;
+        if !is_file_manifest(record.action().entry_type())? {
+            return Ok(
+                ValidateCallbackResult::Invalid(
+                    String::from("Post attachments must be FileManifests"),
+                ),
+            );
+        }
+    }
+    crate::validate_rate_limit(&action)
 }
 pub fn validate_update_post(
     _action: Update,
@@ -208,3 +409,125 @@ pub fn validate_delete_link_posts_by_author_entry_hash(
     // TODO: add the appropriate validation rules
     Ok(ValidateCallbackResult::Valid)
 }
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn post(title: &str, needs: &[&str]) -> Post {
+        Post {
+            title: title.to_string(),
+            needs: needs.iter().map(|need| need.to_string()).collect(),
+            body: String::new(),
+            format: ContentFormat::Plain,
+            attachments: vec![],
+        }
+    }
+    #[test]
+    fn valid_post_passes() {
+        assert_eq!(
+            check_post_fields(&post("A title", &["A need"]), &PostLimits::default()),
+            None
+        );
+    }
+    #[test]
+    fn empty_title_is_rejected() {
+        assert_eq!(
+            check_post_fields(&post("", &[]), &PostLimits::default()),
+            Some(
+                "Post title is 0 characters long, but must be between 1 and 200 characters long"
+                    .to_string()
+            )
+        );
+    }
+    #[test]
+    fn long_title_is_rejected() {
+        let limits = PostLimits {
+            title_max_length: 5,
+            ..Default::default()
+        };
+        assert_eq!(
+            check_post_fields(&post("Too long", &[]), &limits),
+            Some(
+                "Post title is 8 characters long, but must be between 1 and 5 characters long"
+                    .to_string()
+            )
+        );
+    }
+    #[test]
+    fn title_length_counts_characters_not_bytes() {
+        let limits = PostLimits {
+            title_max_length: 3,
+            ..Default::default()
+        };
+        assert_eq!(check_post_fields(&post("äöü", &[]), &limits), None);
+    }
+    #[test]
+    fn too_many_needs_are_rejected() {
+        let limits = PostLimits {
+            max_needs: 1,
+            ..Default::default()
+        };
+        assert_eq!(
+            check_post_fields(&post("Title", &["a", "b"]), &limits),
+            Some("Post has 2 needs, but at most 1 are allowed".to_string())
+        );
+    }
+    #[test]
+    fn long_need_is_rejected() {
+        let limits = PostLimits {
+            need_max_length: 2,
+            ..Default::default()
+        };
+        assert_eq!(
+            check_post_fields(&post("Title", &["ok", "too long"]), &limits),
+            Some("Post need 1 is 8 characters long, but at most 2 are allowed".to_string())
+        );
+    }
+    #[test]
+    fn control_characters_are_rejected() {
+        assert_eq!(
+            check_post_fields(&post("Title\n", &[]), &PostLimits::default()),
+            Some("Post title contains the disallowed character '\\n'".to_string())
+        );
+    }
+    #[test]
+    fn characters_outside_the_allowed_set_are_rejected() {
+        let limits = PostLimits {
+            allowed_characters: Some("abc ".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(check_post_fields(&post("abc cab", &["ba"]), &limits), None);
+        assert_eq!(
+            check_post_fields(&post("abc", &["abd"]), &limits),
+            Some("Post need 0 contains the disallowed character 'd'".to_string())
+        );
+    }
+    #[test]
+    fn body_size_counts_bytes() {
+        let limits = PostLimits {
+            body_max_bytes: 4,
+            ..Default::default()
+        };
+        let mut long_body = post("Title", &[]);
+        long_body.body = "äöü".to_string();
+        assert_eq!(
+            check_post_fields(&long_body, &limits),
+            Some("Post body is 6 bytes long, but at most 4 are allowed".to_string())
+        );
+    }
+    #[test]
+    fn too_many_attachments_are_rejected() {
+        let limits = PostLimits {
+            max_attachments: 1,
+            ..Default::default()
+        };
+        let mut with_attachments = post("Title", &[]);
+        with_attachments.attachments = vec![
+            ActionHash::from_raw_36(vec![0; 36]),
+            ActionHash::from_raw_36(vec![1; 36]),
+        ];
+        assert_eq!(
+            check_post_fields(&with_attachments, &limits),
+            Some("Post has 2 attachments, but at most 1 are allowed".to_string())
+        );
+    }
+}
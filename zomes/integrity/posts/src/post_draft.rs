@@ -0,0 +1,27 @@
+use hdi::prelude::*;
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct PostDraft {
+    pub post: crate::Post,
+}
+pub fn validate_create_post_draft(
+    _action: EntryCreationAction,
+    _post_draft: PostDraft,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_update_post_draft(
+    _action: Update,
+    _post_draft: PostDraft,
+    _original_action: EntryCreationAction,
+    _original_post_draft: PostDraft,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_delete_post_draft(
+    _action: Delete,
+    _original_action: EntryCreationAction,
+    _original_post_draft: PostDraft,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Valid)
+}
@@ -0,0 +1,30 @@
+use hdi::prelude::*;
+#[derive(Serialize, Deserialize, SerializedBytes, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct PostsProperties {
+    pub scheduled_posts_interval_secs: u64,
+    pub moderators: Vec<AgentPubKey>,
+    pub moderator_certificate_type: Option<crate::CertificateType>,
+    pub rate_limit: Option<crate::RateLimit>,
+    pub post_limits: crate::PostLimits,
+    pub previous_dna_hash: Option<DnaHash>,
+}
+impl Default for PostsProperties {
+    fn default() -> Self {
+        Self {
+            scheduled_posts_interval_secs: 60,
+            moderators: vec![],
+            moderator_certificate_type: None,
+            rate_limit: None,
+            post_limits: crate::PostLimits::default(),
+            previous_dna_hash: None,
+        }
+    }
+}
+pub fn posts_properties() -> ExternResult<PostsProperties> {
+    let properties = dna_info()?.modifiers.properties;
+    let properties: Option<PostsProperties> = ExternIO(properties.bytes().clone())
+        .decode()
+        .map_err(|e| wasm_error!(e))?;
+    Ok(properties.unwrap_or_default())
+}
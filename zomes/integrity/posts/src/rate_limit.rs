@@ -0,0 +1,65 @@
+use hdi::prelude::*;
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RateLimit {
+    pub max_creates: usize,
+    pub window_secs: u64,
+}
+pub fn validate_rate_limit(action: &EntryCreationAction) -> ExternResult<ValidateCallbackResult> {
+    let EntryCreationAction::Create(create) = action else {
+        return Ok(ValidateCallbackResult::Valid);
+    };
+    let Some(rate_limit) = crate::posts_properties()?.rate_limit else {
+        return Ok(ValidateCallbackResult::Valid);
+    };
+    let window_start = (create.timestamp - std::time::Duration::from_secs(rate_limit.window_secs))
+        .unwrap_or(Timestamp::from_micros(0));
+    let limited_entry_types: Vec<EntryType> = vec![
+        crate::UnitEntryTypes::Post.try_into()?,
+        crate::UnitEntryTypes::Comment.try_into()?,
+    ];
+    // Walks back through the chain a few actions at a time, and stops as soon as it leaves the
+    // window or the limit is reached
+    let page_size = (rate_limit.max_creates as u32).saturating_add(1).saturating_mul(4);
+    let mut recent_creates = 0;
+    let mut cursor = create.prev_action.clone();
+    loop {
+        let activity = must_get_agent_activity(
+            create.author.clone(),
+            ChainFilter::new(cursor).take(page_size),
+        )?;
+        recent_creates += activity
+            .iter()
+            .filter(|item| match item.action.action() {
+                Action::Create(previous_create) => {
+                    previous_create.timestamp >= window_start
+                        && limited_entry_types.contains(&previous_create.entry_type)
+                }
+                _ => false,
+            })
+            .count();
+        let Some(oldest) = activity.iter().min_by_key(|item| item.action.action().action_seq())
+        else {
+            break;
+        };
+        let left_window = oldest.action.action().timestamp() < window_start;
+        match oldest.action.action().prev_action() {
+            Some(prev_action)
+                if !left_window && recent_creates < rate_limit.max_creates
+                    && activity.len() as u32 == page_size => {
+                cursor = prev_action.clone();
+            }
+            _ => break,
+        }
+    }
+    if recent_creates >= rate_limit.max_creates {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                format!(
+                    "Rate limit exceeded: at most {} posts and comments can be created every {} seconds",
+                    rate_limit.max_creates, rate_limit.window_secs
+                ),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
@@ -0,0 +1,200 @@
+use hdi::prelude::*;
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub enum ReportReason {
+    Spam,
+    Harassment,
+    Misinformation,
+    Other { description: String },
+}
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct Report {
+    pub target_hash: ActionHash,
+    pub reason: ReportReason,
+}
+// An agent reporting the same item more than once can't be ruled out here without walking their
+// whole chain, so counts of reports only consider one report per agent instead
+pub fn validate_create_report(
+    _action: EntryCreationAction,
+    report: Report,
+) -> ExternResult<ValidateCallbackResult> {
+    let record = must_get_valid_record(report.target_hash.clone())?;
+    let post_entry_type: EntryType = crate::UnitEntryTypes::Post.try_into()?;
+    let comment_entry_type: EntryType = crate::UnitEntryTypes::Comment.try_into()?;
+    let entry_type = record.action().entry_type();
+    if entry_type != Some(&post_entry_type) && entry_type != Some(&comment_entry_type) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Only posts and comments can be reported"),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_update_report(
+    _action: Update,
+    _report: Report,
+    _original_action: EntryCreationAction,
+    _original_report: Report,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from("Reports cannot be updated")))
+}
+pub fn validate_delete_report(
+    _action: Delete,
+    _original_action: EntryCreationAction,
+    _original_report: Report,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Invalid(String::from("Reports cannot be deleted")))
+}
+fn validate_report_link(
+    action: &CreateLink,
+    reported_hash: ActionHash,
+    report_hash: ActionHash,
+) -> ExternResult<ValidateCallbackResult> {
+    let record = must_get_valid_record(report_hash)?;
+    let report: crate::Report = record
+        .entry()
+        .to_app_option()
+        .map_err(|e| wasm_error!(e))?
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("Linked action must reference an entry"
+                .to_string())
+            ),
+        )?;
+    if report.target_hash.ne(&reported_hash) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Reports must be linked from the item they report"),
+            ),
+        );
+    }
+    if record.action().author().ne(&action.author) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("Only the reporter can link their Reports"),
+            ),
+        );
+    }
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_create_link_post_to_reports(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let post_hash = base_address
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("No action hash associated with link".to_string())
+            ),
+        )?;
+    let record = must_get_valid_record(post_hash.clone())?;
+    let post_entry_type: EntryType = crate::UnitEntryTypes::Post.try_into()?;
+    if record.action().entry_type() != Some(&post_entry_type) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("PostToReports links must start from a Post"),
+            ),
+        );
+    }
+    let report_hash = target_address
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("No action hash associated with link".to_string())
+            ),
+        )?;
+    validate_report_link(&action, post_hash, report_hash)
+}
+pub fn validate_delete_link_post_to_reports(
+    _action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(
+        ValidateCallbackResult::Invalid(
+            String::from("PostToReports links cannot be deleted"),
+        ),
+    )
+}
+pub fn validate_create_link_comment_to_reports(
+    action: CreateLink,
+    base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let comment_hash = base_address
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("No action hash associated with link".to_string())
+            ),
+        )?;
+    let record = must_get_valid_record(comment_hash.clone())?;
+    let comment_entry_type: EntryType = crate::UnitEntryTypes::Comment.try_into()?;
+    if record.action().entry_type() != Some(&comment_entry_type) {
+        return Ok(
+            ValidateCallbackResult::Invalid(
+                String::from("CommentToReports links must start from a Comment"),
+            ),
+        );
+    }
+    let report_hash = target_address
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("No action hash associated with link".to_string())
+            ),
+        )?;
+    validate_report_link(&action, comment_hash, report_hash)
+}
+pub fn validate_delete_link_comment_to_reports(
+    _action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(
+        ValidateCallbackResult::Invalid(
+            String::from("CommentToReports links cannot be deleted"),
+        ),
+    )
+}
+pub fn validate_create_link_reported_items(
+    action: CreateLink,
+    _base_address: AnyLinkableHash,
+    target_address: AnyLinkableHash,
+    tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    let reported_hash = target_address
+        .into_action_hash()
+        .ok_or(
+            wasm_error!(
+                WasmErrorInner::Guest("No action hash associated with link".to_string())
+            ),
+        )?;
+    let report_hash: ActionHash = ExternIO(tag.0)
+        .decode()
+        .map_err(|e| wasm_error!(e))?;
+    validate_report_link(&action, reported_hash, report_hash)
+}
+pub fn validate_delete_link_reported_items(
+    _action: DeleteLink,
+    _original_action: CreateLink,
+    _base: AnyLinkableHash,
+    _target: AnyLinkableHash,
+    _tag: LinkTag,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(
+        ValidateCallbackResult::Invalid(
+            String::from("ReportedItems links cannot be deleted"),
+        ),
+    )
+}
@@ -0,0 +1,28 @@
+use hdi::prelude::*;
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct ScheduledPost {
+    pub post: crate::Post,
+    pub publish_at: Timestamp,
+}
+pub fn validate_create_scheduled_post(
+    _action: EntryCreationAction,
+    _scheduled_post: ScheduledPost,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_update_scheduled_post(
+    _action: Update,
+    _scheduled_post: ScheduledPost,
+    _original_action: EntryCreationAction,
+    _original_scheduled_post: ScheduledPost,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_delete_scheduled_post(
+    _action: Delete,
+    _original_action: EntryCreationAction,
+    _original_scheduled_post: ScheduledPost,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Valid)
+}
@@ -0,0 +1,29 @@
+use hdi::prelude::*;
+#[hdk_entry_helper]
+#[derive(Clone, PartialEq)]
+pub struct Subscription {
+    pub entry_types: Option<Vec<crate::UnitEntryTypes>>,
+    pub link_types: Option<Vec<crate::LinkTypes>>,
+    pub post_hashes: Option<Vec<ActionHash>>,
+}
+pub fn validate_create_subscription(
+    _action: EntryCreationAction,
+    _subscription: Subscription,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_update_subscription(
+    _action: Update,
+    _subscription: Subscription,
+    _original_action: EntryCreationAction,
+    _original_subscription: Subscription,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Valid)
+}
+pub fn validate_delete_subscription(
+    _action: Delete,
+    _original_action: EntryCreationAction,
+    _original_subscription: Subscription,
+) -> ExternResult<ValidateCallbackResult> {
+    Ok(ValidateCallbackResult::Valid)
+}
@@ -0,0 +1,250 @@
+use hdi::prelude::*;
+
+use posts_integrity::*;
+
+mod common;
+use common::*;
+
+fn certificate(
+    dht: &TestDht,
+    post: &Record,
+    agent: &AgentPubKey,
+    certifications_hashes: Vec<EntryHash>,
+) -> Certificate {
+    Certificate {
+        post_hash: post.action_address().clone(),
+        agent: agent.clone(),
+        certifications_hashes,
+        certificate_type: CertificateType::TypeOne,
+        dna_hash: dht.dna_hash(),
+    }
+}
+
+#[test]
+fn create_certificate_is_valid() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+    let bob = dht.agent();
+
+    let post = dht.create(&alice, EntryTypes::Post(sample_post()));
+    let record = dht.create(
+        &alice,
+        EntryTypes::Certificate(certificate(&dht, &post, &bob, vec![])),
+    );
+
+    for op in dht.ops_for(&record) {
+        assert_valid(op);
+    }
+}
+
+#[test]
+fn create_certificate_with_certifications_is_valid() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+    let bob = dht.agent();
+    let carol = dht.agent();
+
+    let post = dht.create(&alice, EntryTypes::Post(sample_post()));
+    let bob_certificate = dht.create(
+        &alice,
+        EntryTypes::Certificate(certificate(&dht, &post, &bob, vec![])),
+    );
+    let record = dht.create(
+        &bob,
+        EntryTypes::Certificate(certificate(
+            &dht,
+            &post,
+            &carol,
+            vec![bob_certificate.action().entry_hash().unwrap().clone()],
+        )),
+    );
+
+    for op in dht.ops_for(&record) {
+        assert_valid(op);
+    }
+}
+
+#[test]
+fn create_certificate_for_missing_post_is_unresolved() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+    let bob = dht.agent();
+
+    let post = dht.create(&alice, EntryTypes::Post(sample_post()));
+    let record = dht.create(
+        &alice,
+        EntryTypes::Certificate(certificate(&dht, &post, &bob, vec![])),
+    );
+    dht.forget(&post);
+
+    assert_unresolved(dht.store_entry(&record), post.action_address().clone());
+    assert_unresolved(dht.store_record(&record), post.action_address().clone());
+}
+
+#[test]
+fn create_certificate_with_missing_certification_is_unresolved() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+    let bob = dht.agent();
+
+    let post = dht.create(&alice, EntryTypes::Post(sample_post()));
+    let missing = dht.fresh_entry_hash();
+    let record = dht.create(
+        &alice,
+        EntryTypes::Certificate(certificate(&dht, &post, &bob, vec![missing.clone()])),
+    );
+
+    assert_unresolved(dht.store_record(&record), missing);
+}
+
+#[test]
+fn create_certificate_with_a_post_as_certification_is_rejected() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+    let bob = dht.agent();
+
+    let post = dht.create(&alice, EntryTypes::Post(sample_post()));
+    let post_entry_hash = post.action().entry_hash().unwrap().clone();
+    let record = dht.create(
+        &alice,
+        EntryTypes::Certificate(certificate(&dht, &post, &bob, vec![post_entry_hash])),
+    );
+
+    assert!(run_validate(dht.store_record(&record)).is_err());
+}
+
+#[test]
+fn update_certificate_is_invalid() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+    let bob = dht.agent();
+
+    let post = dht.create(&alice, EntryTypes::Post(sample_post()));
+    let original = dht.create(
+        &alice,
+        EntryTypes::Certificate(certificate(&dht, &post, &bob, vec![])),
+    );
+    let mut updated = certificate(&dht, &post, &bob, vec![]);
+    updated.certificate_type = CertificateType::TypeTwo;
+    let record = dht.update(&alice, &original, EntryTypes::Certificate(updated));
+
+    assert_invalid(dht.store_record(&record), "Certificates cannot be updated");
+}
+
+#[test]
+fn delete_certificate_is_invalid() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+    let bob = dht.agent();
+
+    let post = dht.create(&alice, EntryTypes::Post(sample_post()));
+    let original = dht.create(
+        &alice,
+        EntryTypes::Certificate(certificate(&dht, &post, &bob, vec![])),
+    );
+    let record = dht.delete(&alice, &original);
+
+    assert_invalid(dht.store_record(&record), "Certificates cannot be deleted");
+}
+
+#[test]
+fn certificate_links_are_valid_and_cannot_be_deleted() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+    let bob = dht.agent();
+    let carol = dht.agent();
+
+    let post = dht.create(&alice, EntryTypes::Post(sample_post()));
+    let bob_certificate = dht.create(
+        &alice,
+        EntryTypes::Certificate(certificate(&dht, &post, &bob, vec![])),
+    );
+    let bob_certificate_entry_hash = bob_certificate.action().entry_hash().unwrap().clone();
+    let carol_certificate = dht.create(
+        &bob,
+        EntryTypes::Certificate(certificate(
+            &dht,
+            &post,
+            &carol,
+            vec![bob_certificate_entry_hash.clone()],
+        )),
+    );
+
+    let links = [
+        (
+            dht.create_link(
+                &alice,
+                post.action_address().clone(),
+                bob_certificate.action_address().clone(),
+                LinkTypes::PostToCertificates,
+            ),
+            "PostToCertificates links cannot be deleted",
+        ),
+        (
+            dht.create_link(
+                &alice,
+                bob.clone(),
+                bob_certificate.action_address().clone(),
+                LinkTypes::CertifiedToCertificates,
+            ),
+            "CertifiedToCertificates links cannot be deleted",
+        ),
+        (
+            dht.create_link(
+                &bob,
+                bob_certificate_entry_hash,
+                carol_certificate.action_address().clone(),
+                LinkTypes::CertificateToCertificates,
+            ),
+            "CertificateToCertificates links cannot be deleted",
+        ),
+    ];
+
+    for (create_link, reason) in links {
+        for op in dht.ops_for(&create_link) {
+            assert_valid(op);
+        }
+        let author = create_link.action().author().clone();
+        let record = dht.delete_link(&author, &create_link);
+        assert_invalid(dht.register_delete_link(&record), reason);
+        assert_invalid(dht.store_record(&record), reason);
+    }
+}
+
+#[test]
+fn certificate_to_certificates_link_from_missing_certificate_is_unresolved() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+    let bob = dht.agent();
+
+    let post = dht.create(&alice, EntryTypes::Post(sample_post()));
+    let bob_certificate = dht.create(
+        &alice,
+        EntryTypes::Certificate(certificate(&dht, &post, &bob, vec![])),
+    );
+    let missing = dht.fresh_entry_hash();
+    let record = dht.create_link(
+        &bob,
+        missing.clone(),
+        bob_certificate.action_address().clone(),
+        LinkTypes::CertificateToCertificates,
+    );
+
+    assert_unresolved(dht.register_create_link(&record), missing);
+}
+
+#[test]
+fn post_to_certificates_link_to_a_post_is_rejected() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+
+    let post = dht.create(&alice, EntryTypes::Post(sample_post()));
+    let record = dht.create_link(
+        &alice,
+        post.action_address().clone(),
+        post.action_address().clone(),
+        LinkTypes::PostToCertificates,
+    );
+
+    assert!(run_validate(dht.register_create_link(&record)).is_err());
+}
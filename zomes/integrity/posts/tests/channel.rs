@@ -0,0 +1,270 @@
+use hdi::prelude::*;
+
+use posts_integrity::*;
+
+mod common;
+use common::*;
+
+fn members_channel(members: Vec<AgentPubKey>) -> Channel {
+    Channel {
+        name: "Members".to_string(),
+        description: "Only for members".to_string(),
+        posting_policy: PostingPolicy::Members { members },
+    }
+}
+
+#[test]
+fn create_channel_is_valid() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+
+    let channel = dht.create(&alice, EntryTypes::Channel(members_channel(vec![])));
+    let path_hash = dht.fresh_entry_hash();
+    let link = dht.create_link(
+        &alice,
+        path_hash,
+        channel.action_address().clone(),
+        LinkTypes::AllChannels,
+    );
+
+    for op in dht.ops_for(&channel).into_iter().chain(dht.ops_for(&link)) {
+        assert_valid(op);
+    }
+}
+
+#[test]
+fn channels_cannot_be_updated_or_deleted() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+
+    let channel = dht.create(&alice, EntryTypes::Channel(members_channel(vec![])));
+    let update = dht.update(
+        &alice,
+        &channel,
+        EntryTypes::Channel(members_channel(vec![alice.clone()])),
+    );
+    let delete = dht.delete(&alice, &channel);
+
+    assert_invalid(dht.store_record(&update), "Channels cannot be updated");
+    assert_invalid(dht.store_record(&delete), "Channels cannot be deleted");
+}
+
+#[test]
+fn members_and_creator_can_post_to_a_members_channel() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+    let bob = dht.agent();
+
+    let channel = dht.create(&alice, EntryTypes::Channel(members_channel(vec![bob.clone()])));
+    for author in [&alice, &bob] {
+        let post = dht.create(author, EntryTypes::Post(sample_post()));
+        let link = dht.create_link(
+            author,
+            channel.action_address().clone(),
+            post.action_address().clone(),
+            LinkTypes::ChannelToPosts,
+        );
+
+        for op in dht.ops_for(&link) {
+            assert_valid(op);
+        }
+    }
+}
+
+#[test]
+fn non_members_cannot_post_to_a_members_channel() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+    let carol = dht.agent();
+
+    let channel = dht.create(&alice, EntryTypes::Channel(members_channel(vec![])));
+    let post = dht.create(&carol, EntryTypes::Post(sample_post()));
+    let link = dht.create_link(
+        &carol,
+        channel.action_address().clone(),
+        post.action_address().clone(),
+        LinkTypes::ChannelToPosts,
+    );
+
+    let reason =
+        "The posting policy of Channel \"Members\" does not allow this agent to post to it";
+    assert_invalid(dht.register_create_link(&link), reason);
+    assert_invalid(dht.store_record(&link), reason);
+}
+
+#[test]
+fn only_moderators_can_post_to_a_moderators_channel() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+    let moderator = dht.agent();
+    let carol = dht.agent();
+    dht.set_properties(PostsProperties {
+        moderators: vec![moderator.clone()],
+        ..Default::default()
+    });
+
+    let channel = dht.create(
+        &alice,
+        EntryTypes::Channel(Channel {
+            name: "Announcements".to_string(),
+            description: String::new(),
+            posting_policy: PostingPolicy::Moderators,
+        }),
+    );
+    let moderator_post = dht.create(&moderator, EntryTypes::Post(sample_post()));
+    let moderator_link = dht.create_link(
+        &moderator,
+        channel.action_address().clone(),
+        moderator_post.action_address().clone(),
+        LinkTypes::ChannelToPosts,
+    );
+    let carol_post = dht.create(&carol, EntryTypes::Post(sample_post()));
+    let carol_link = dht.create_link(
+        &carol,
+        channel.action_address().clone(),
+        carol_post.action_address().clone(),
+        LinkTypes::ChannelToPosts,
+    );
+
+    assert_valid(dht.store_record(&moderator_link));
+    assert_invalid(
+        dht.store_record(&carol_link),
+        "The posting policy of Channel \"Announcements\" does not allow this agent to post to it",
+    );
+}
+
+#[test]
+fn moderators_by_certificate_can_post_to_a_moderators_channel() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+    let moderator = dht.agent();
+    let dave = dht.agent();
+    dht.set_properties(PostsProperties {
+        moderators: vec![moderator.clone()],
+        moderator_certificate_type: Some(CertificateType::TypeOne),
+        ..Default::default()
+    });
+
+    let channel = dht.create(
+        &alice,
+        EntryTypes::Channel(Channel {
+            name: "Announcements".to_string(),
+            description: String::new(),
+            posting_policy: PostingPolicy::Moderators,
+        }),
+    );
+    let moderator_post = dht.create(&moderator, EntryTypes::Post(sample_post()));
+    let certificate = dht.create(
+        &moderator,
+        EntryTypes::Certificate(Certificate {
+            post_hash: moderator_post.action_address().clone(),
+            agent: dave.clone(),
+            certifications_hashes: vec![],
+            certificate_type: CertificateType::TypeOne,
+            dna_hash: dht.dna_hash(),
+        }),
+    );
+    let dave_post = dht.create(&dave, EntryTypes::Post(sample_post()));
+    let certified_link = dht.create_link_with_tag(
+        &dave,
+        channel.action_address().clone(),
+        dave_post.action_address().clone(),
+        LinkTypes::ChannelToPosts,
+        LinkTag::new(ExternIO::encode(certificate.action_address().clone()).unwrap().0),
+    );
+    let uncertified_link = dht.create_link(
+        &dave,
+        channel.action_address().clone(),
+        dave_post.action_address().clone(),
+        LinkTypes::ChannelToPosts,
+    );
+
+    assert_valid(dht.store_record(&certified_link));
+    assert_invalid(
+        dht.store_record(&uncertified_link),
+        "The posting policy of Channel \"Announcements\" does not allow this agent to post to it",
+    );
+}
+
+#[test]
+fn only_the_author_of_a_post_can_add_it_to_a_channel() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+    let bob = dht.agent();
+
+    let channel = dht.create(
+        &alice,
+        EntryTypes::Channel(Channel {
+            posting_policy: PostingPolicy::Anyone,
+            ..members_channel(vec![])
+        }),
+    );
+    let post = dht.create(&bob, EntryTypes::Post(sample_post()));
+    let link = dht.create_link(
+        &alice,
+        channel.action_address().clone(),
+        post.action_address().clone(),
+        LinkTypes::ChannelToPosts,
+    );
+
+    assert_invalid(
+        dht.store_record(&link),
+        "Only the author of a Post can add it to a Channel",
+    );
+}
+
+#[test]
+fn channel_to_posts_links_must_point_from_a_channel_to_a_post() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+
+    let channel = dht.create(&alice, EntryTypes::Channel(members_channel(vec![])));
+    let post = dht.create(&alice, EntryTypes::Post(sample_post()));
+
+    let from_post = dht.create_link(
+        &alice,
+        post.action_address().clone(),
+        post.action_address().clone(),
+        LinkTypes::ChannelToPosts,
+    );
+    let to_channel = dht.create_link(
+        &alice,
+        channel.action_address().clone(),
+        channel.action_address().clone(),
+        LinkTypes::ChannelToPosts,
+    );
+
+    assert_invalid(
+        dht.store_record(&from_post),
+        "ChannelToPosts links must start from a Channel",
+    );
+    assert_invalid(
+        dht.store_record(&to_channel),
+        "ChannelToPosts links must point to a Post",
+    );
+}
+
+#[test]
+fn only_the_link_author_can_remove_a_post_from_a_channel() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+    let bob = dht.agent();
+
+    let channel = dht.create(&alice, EntryTypes::Channel(members_channel(vec![bob.clone()])));
+    let post = dht.create(&bob, EntryTypes::Post(sample_post()));
+    let link = dht.create_link(
+        &bob,
+        channel.action_address().clone(),
+        post.action_address().clone(),
+        LinkTypes::ChannelToPosts,
+    );
+
+    let by_alice = dht.delete_link(&alice, &link);
+    let by_bob = dht.delete_link(&bob, &link);
+
+    assert_invalid(
+        dht.register_delete_link(&by_alice),
+        "Only the author of a Post can remove it from a Channel",
+    );
+    assert_valid(dht.register_delete_link(&by_bob));
+}
@@ -0,0 +1,184 @@
+use hdi::prelude::*;
+
+use posts_integrity::*;
+
+mod common;
+use common::*;
+
+fn post_and_comment(dht: &mut TestDht, author: &AgentPubKey) -> (Record, Record) {
+    let post = dht.create(author, EntryTypes::Post(sample_post()));
+    let comment = dht.create(
+        author,
+        EntryTypes::Comment(Comment {
+            post_hash: post.action_address().clone(),
+        }),
+    );
+    (post, comment)
+}
+
+#[test]
+fn create_comment_is_valid() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+    let bob = dht.agent();
+
+    let post = dht.create(&alice, EntryTypes::Post(sample_post()));
+    let record = dht.create(
+        &bob,
+        EntryTypes::Comment(Comment {
+            post_hash: post.action_address().clone(),
+        }),
+    );
+
+    for op in dht.ops_for(&record) {
+        assert_valid(op);
+    }
+}
+
+#[test]
+fn create_comment_for_missing_post_is_unresolved() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+
+    let missing = dht.fresh_action_hash();
+    let record = dht.create(
+        &alice,
+        EntryTypes::Comment(Comment {
+            post_hash: missing.clone(),
+        }),
+    );
+
+    assert_unresolved(dht.store_entry(&record), missing.clone());
+    assert_unresolved(dht.store_record(&record), missing);
+}
+
+#[test]
+fn create_comment_for_a_comment_is_rejected() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+
+    let (_post, comment) = post_and_comment(&mut dht, &alice);
+    let record = dht.create(
+        &alice,
+        EntryTypes::Comment(Comment {
+            post_hash: comment.action_address().clone(),
+        }),
+    );
+
+    assert_invalid(dht.store_record(&record), "Comments can only be made on Posts");
+}
+
+#[test]
+fn create_comment_counts_towards_rate_limit() {
+    let mut dht = TestDht::with_properties(PostsProperties {
+        rate_limit: Some(RateLimit {
+            max_creates: 2,
+            window_secs: 60,
+        }),
+        ..Default::default()
+    });
+    let alice = dht.agent();
+
+    let (post, _comment) = post_and_comment(&mut dht, &alice);
+    let record = dht.create(
+        &alice,
+        EntryTypes::Comment(Comment {
+            post_hash: post.action_address().clone(),
+        }),
+    );
+
+    assert_invalid(
+        dht.store_record(&record),
+        "Rate limit exceeded: at most 2 posts and comments can be created every 60 seconds",
+    );
+}
+
+#[test]
+fn update_comment_is_valid() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+
+    let (post, comment) = post_and_comment(&mut dht, &alice);
+    let record = dht.update(
+        &alice,
+        &comment,
+        EntryTypes::Comment(Comment {
+            post_hash: post.action_address().clone(),
+        }),
+    );
+
+    for op in dht.ops_for(&record) {
+        assert_valid(op);
+    }
+}
+
+#[test]
+fn delete_comment_is_valid() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+
+    let (_post, comment) = post_and_comment(&mut dht, &alice);
+    let record = dht.delete(&alice, &comment);
+
+    for op in dht.ops_for(&record) {
+        assert_valid(op);
+    }
+}
+
+#[test]
+fn post_to_comments_link_is_valid() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+
+    let (post, comment) = post_and_comment(&mut dht, &alice);
+    let record = dht.create_link(
+        &alice,
+        post.action_address().clone(),
+        comment.action_address().clone(),
+        LinkTypes::PostToComments,
+    );
+
+    for op in dht.ops_for(&record) {
+        assert_valid(op);
+    }
+
+    let record = dht.delete_link(&alice, &record);
+
+    for op in dht.ops_for(&record) {
+        assert_valid(op);
+    }
+}
+
+#[test]
+fn post_to_comments_link_with_swapped_base_and_target_is_rejected() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+
+    let (post, comment) = post_and_comment(&mut dht, &alice);
+    let record = dht.create_link(
+        &alice,
+        comment.action_address().clone(),
+        post.action_address().clone(),
+        LinkTypes::PostToComments,
+    );
+
+    let reason = "PostToComments links must start from a Post";
+    assert_invalid(dht.register_create_link(&record), reason);
+    assert_invalid(dht.store_record(&record), reason);
+}
+
+#[test]
+fn post_to_comments_link_from_an_agent_is_rejected() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+
+    let (_post, comment) = post_and_comment(&mut dht, &alice);
+    let record = dht.create_link(
+        &alice,
+        alice.clone(),
+        comment.action_address().clone(),
+        LinkTypes::PostToComments,
+    );
+
+    assert!(run_validate(dht.register_create_link(&record)).is_err());
+}
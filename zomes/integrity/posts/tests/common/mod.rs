@@ -0,0 +1,615 @@
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use hdi::hdi::{set_hdi, MockHdiT};
+use hdi::prelude::*;
+
+use posts_integrity::*;
+
+pub mod strategies;
+
+#[derive(Default)]
+struct Dht {
+    records: HashMap<ActionHash, Record>,
+    entries: HashMap<EntryHash, Entry>,
+    chains: HashMap<AgentPubKey, Vec<SignedActionHashed>>,
+    properties: PostsProperties,
+    // Entries that were hashed without being committed, which can't be fetched
+    hashed_entries: HashMap<EntryHash, Entry>,
+}
+
+impl Dht {
+    fn find_entry_hash(&self, entry: &Entry) -> Option<EntryHash> {
+        self.entries
+            .iter()
+            .chain(self.hashed_entries.iter())
+            .find(|(_, existing)| *existing == entry)
+            .map(|(entry_hash, _)| entry_hash.clone())
+    }
+}
+
+/// An in-memory stand-in for the DHT and the source chains of its agents.
+///
+/// Creating one installs a mocked HDI on the current thread, so that `validate` resolves
+/// `must_get_*` calls against the records committed here instead of against a conductor.
+pub struct TestDht {
+    dht: Arc<Mutex<Dht>>,
+    dna_hash: DnaHash,
+    next_hash: u32,
+    now: Timestamp,
+}
+
+impl TestDht {
+    pub fn new() -> Self {
+        Self::with_properties(PostsProperties::default())
+    }
+
+    pub fn with_properties(properties: PostsProperties) -> Self {
+        let dht = Arc::new(Mutex::new(Dht {
+            properties,
+            ..Default::default()
+        }));
+        let dna_hash = DnaHash::from_raw_36(vec![0; 36]);
+
+        let mut mock = MockHdiT::new();
+        mock.expect_zome_info().returning(|_| Ok(posts_zome_info()));
+        let state = dht.clone();
+        let hash = dna_hash.clone();
+        mock.expect_dna_info().returning(move |_| {
+            Ok(posts_dna_info(
+                hash.clone(),
+                &state.lock().unwrap().properties,
+            ))
+        });
+        let state = dht.clone();
+        mock.expect_must_get_valid_record().returning(
+            move |MustGetValidRecordInput(action_hash)| {
+                state
+                    .lock()
+                    .unwrap()
+                    .records
+                    .get(&action_hash)
+                    .cloned()
+                    .ok_or_else(|| unresolved(action_hash))
+            },
+        );
+        let state = dht.clone();
+        mock.expect_must_get_action()
+            .returning(move |MustGetActionInput(action_hash)| {
+                state
+                    .lock()
+                    .unwrap()
+                    .records
+                    .get(&action_hash)
+                    .map(|record| record.signed_action.clone())
+                    .ok_or_else(|| unresolved(action_hash))
+            });
+        let state = dht.clone();
+        mock.expect_must_get_entry()
+            .returning(move |MustGetEntryInput(entry_hash)| {
+                state
+                    .lock()
+                    .unwrap()
+                    .entries
+                    .get(&entry_hash)
+                    .map(|entry| EntryHashed::with_pre_hashed(entry.clone(), entry_hash.clone()))
+                    .ok_or_else(|| unresolved(entry_hash))
+            });
+        let state = dht.clone();
+        mock.expect_must_get_agent_activity().returning(
+            move |MustGetAgentActivityInput {
+                      author,
+                      chain_filter,
+                  }| {
+                let dht = state.lock().unwrap();
+                let chain = dht.chains.get(&author).cloned().unwrap_or_default();
+                let Some(top) = chain
+                    .iter()
+                    .position(|action| action.action_address().eq(&chain_filter.chain_top))
+                else {
+                    return Err(short_circuit(UnresolvedDependencies::AgentActivity(
+                        author,
+                        chain_filter,
+                    )));
+                };
+                let take = match &chain_filter.filters {
+                    ChainFilters::Take(take) | ChainFilters::Both(take, _) => *take as usize,
+                    _ => usize::MAX,
+                };
+                Ok(chain[..=top]
+                    .iter()
+                    .rev()
+                    .take(take)
+                    .map(|action| RegisterAgentActivity {
+                        action: action.clone(),
+                        cached_entry: None,
+                    })
+                    .collect())
+            },
+        );
+        // Actions and entries committed here have made-up hashes and signatures, so hashing looks
+        // them up, and only the placeholder signature the records are signed with verifies.
+        // Entries that were never committed get a made-up hash of their own, which is kept so
+        // that hashing them again gives the same result
+        let state = dht.clone();
+        mock.expect_hash().returning(move |input| match input {
+            HashInput::Action(action) => state
+                .lock()
+                .unwrap()
+                .records
+                .values()
+                .find(|record| record.action().eq(&action))
+                .map(|record| HashOutput::Action(record.action_address().clone()))
+                .ok_or_else(|| wasm_error!(WasmErrorInner::Guest("Unknown action".into()))),
+            HashInput::Entry(entry) => {
+                let mut dht = state.lock().unwrap();
+                let entry_hash = dht.find_entry_hash(&entry).unwrap_or_else(|| {
+                    let mut raw = vec![0xff];
+                    raw.extend((dht.hashed_entries.len() as u32).to_be_bytes());
+                    raw.resize(36, 0);
+                    let entry_hash = EntryHash::from_raw_36(raw);
+                    dht.hashed_entries.insert(entry_hash.clone(), entry);
+                    entry_hash
+                });
+                Ok(HashOutput::Entry(entry_hash))
+            }
+            _ => Err(wasm_error!(WasmErrorInner::Guest(
+                "Only actions and entries can be hashed in the TestDht".into()
+            ))),
+        });
+        mock.expect_verify_signature()
+            .returning(|VerifySignature { signature, .. }| Ok(signature == Signature([0; 64])));
+        set_hdi(mock);
+
+        Self {
+            dht,
+            dna_hash,
+            next_hash: 0,
+            now: Timestamp::from_micros(1_700_000_000_000_000),
+        }
+    }
+
+    pub fn dna_hash(&self) -> DnaHash {
+        self.dna_hash.clone()
+    }
+
+    // For properties that name agents, which only exist once the DHT does
+    pub fn set_properties(&mut self, properties: PostsProperties) {
+        self.dht.lock().unwrap().properties = properties;
+    }
+
+    pub fn wait(&mut self, duration: Duration) {
+        self.now = (self.now + duration).unwrap();
+    }
+
+    pub fn fresh_action_hash(&mut self) -> ActionHash {
+        ActionHash::from_raw_36(self.next_raw_hash())
+    }
+
+    pub fn fresh_entry_hash(&mut self) -> EntryHash {
+        EntryHash::from_raw_36(self.next_raw_hash())
+    }
+
+    // FileManifests belong to the file_storage_integrity zome, which comes second in the DNA
+    pub fn create_file_manifest(
+        &mut self,
+        author: &AgentPubKey,
+        file_manifest: file_storage_types::FileManifest,
+    ) -> Record {
+        self.create_raw(
+            author,
+            EntryType::App(AppEntryDef::new(
+                EntryDefIndex(file_storage_types::FILE_MANIFEST_ENTRY_INDEX),
+                ZomeIndex(1),
+                EntryVisibility::Public,
+            )),
+            Entry::try_from(&file_manifest).unwrap(),
+        )
+    }
+
+    pub fn forget(&mut self, record: &Record) {
+        let mut dht = self.dht.lock().unwrap();
+        dht.records.remove(record.action_address());
+        if let Some(entry_hash) = record.action().entry_hash() {
+            dht.entries.remove(entry_hash);
+        }
+    }
+
+    pub fn agent(&mut self) -> AgentPubKey {
+        let agent = AgentPubKey::from_raw_36(self.next_raw_hash());
+        let dna = Action::Dna(Dna {
+            author: agent.clone(),
+            timestamp: self.tick(),
+            hash: self.dna_hash.clone(),
+        });
+        self.commit(dna, None);
+        let (action_seq, prev_action) = self.chain_head(&agent);
+        let agent_validation_pkg = Action::AgentValidationPkg(AgentValidationPkg {
+            author: agent.clone(),
+            timestamp: self.tick(),
+            action_seq,
+            prev_action,
+            membrane_proof: None,
+        });
+        self.commit(agent_validation_pkg, None);
+        let (action_seq, prev_action) = self.chain_head(&agent);
+        let create_agent = Action::Create(Create {
+            author: agent.clone(),
+            timestamp: self.tick(),
+            action_seq,
+            prev_action,
+            entry_type: EntryType::AgentPubKey,
+            entry_hash: agent.clone().into(),
+            weight: Default::default(),
+        });
+        self.commit(create_agent, Some(Entry::Agent(agent.clone())));
+        agent
+    }
+
+    pub fn chain_top(&self, agent: &AgentPubKey) -> Record {
+        let dht = self.dht.lock().unwrap();
+        let action = dht.chains[agent].last().unwrap();
+        dht.records[action.action_address()].clone()
+    }
+
+    pub fn create(&mut self, author: &AgentPubKey, app_entry: EntryTypes) -> Record {
+        let entry_type = app_entry_type(&app_entry);
+        let entry = Entry::try_from(&app_entry).unwrap();
+        self.create_raw(author, entry_type, entry)
+    }
+
+    // Commits an entry with any bytes and entry type, such as entries of older schemas
+    pub fn create_raw(&mut self, author: &AgentPubKey, entry_type: EntryType, entry: Entry) -> Record {
+        let entry_hash = self.entry_hash(&entry);
+        let (action_seq, prev_action) = self.chain_head(author);
+        let action = Action::Create(Create {
+            author: author.clone(),
+            timestamp: self.tick(),
+            action_seq,
+            prev_action,
+            entry_type,
+            entry_hash,
+            weight: Default::default(),
+        });
+        self.commit(action, Some(entry))
+    }
+
+    pub fn update(
+        &mut self,
+        author: &AgentPubKey,
+        original: &Record,
+        app_entry: EntryTypes,
+    ) -> Record {
+        let entry_type = app_entry_type(&app_entry);
+        let entry = Entry::try_from(&app_entry).unwrap();
+        let entry_hash = self.entry_hash(&entry);
+        let (action_seq, prev_action) = self.chain_head(author);
+        let action = Action::Update(Update {
+            author: author.clone(),
+            timestamp: self.tick(),
+            action_seq,
+            prev_action,
+            original_action_address: original.action_address().clone(),
+            original_entry_address: original.action().entry_hash().unwrap().clone(),
+            entry_type,
+            entry_hash,
+            weight: Default::default(),
+        });
+        self.commit(action, Some(entry))
+    }
+
+    pub fn delete(&mut self, author: &AgentPubKey, original: &Record) -> Record {
+        let (action_seq, prev_action) = self.chain_head(author);
+        let action = Action::Delete(Delete {
+            author: author.clone(),
+            timestamp: self.tick(),
+            action_seq,
+            prev_action,
+            deletes_address: original.action_address().clone(),
+            deletes_entry_address: original.action().entry_hash().unwrap().clone(),
+            weight: Default::default(),
+        });
+        self.commit(action, None)
+    }
+
+    pub fn create_link(
+        &mut self,
+        author: &AgentPubKey,
+        base_address: impl Into<AnyLinkableHash>,
+        target_address: impl Into<AnyLinkableHash>,
+        link_type: LinkTypes,
+    ) -> Record {
+        self.create_link_with_tag(
+            author,
+            base_address,
+            target_address,
+            link_type,
+            LinkTag::new(Vec::<u8>::new()),
+        )
+    }
+
+    pub fn create_link_with_tag(
+        &mut self,
+        author: &AgentPubKey,
+        base_address: impl Into<AnyLinkableHash>,
+        target_address: impl Into<AnyLinkableHash>,
+        link_type: LinkTypes,
+        tag: LinkTag,
+    ) -> Record {
+        let ScopedLinkType {
+            zome_index,
+            zome_type,
+        } = ScopedLinkType::try_from(link_type).unwrap();
+        let (action_seq, prev_action) = self.chain_head(author);
+        let action = Action::CreateLink(CreateLink {
+            author: author.clone(),
+            timestamp: self.tick(),
+            action_seq,
+            prev_action,
+            base_address: base_address.into(),
+            target_address: target_address.into(),
+            zome_index,
+            link_type: zome_type,
+            tag,
+            weight: Default::default(),
+        });
+        self.commit(action, None)
+    }
+
+    pub fn delete_link(&mut self, author: &AgentPubKey, create_link: &Record) -> Record {
+        let base_address = match create_link.action() {
+            Action::CreateLink(create_link) => create_link.base_address.clone(),
+            _ => panic!("Can only delete a CreateLink"),
+        };
+        let (action_seq, prev_action) = self.chain_head(author);
+        let action = Action::DeleteLink(DeleteLink {
+            author: author.clone(),
+            timestamp: self.tick(),
+            action_seq,
+            prev_action,
+            base_address,
+            link_add_address: create_link.action_address().clone(),
+        });
+        self.commit(action, None)
+    }
+
+    pub fn store_record(&self, record: &Record) -> Op {
+        Op::StoreRecord(StoreRecord {
+            record: record.clone(),
+        })
+    }
+
+    pub fn store_entry(&self, record: &Record) -> Op {
+        let action = match record.action().clone() {
+            Action::Create(create) => EntryCreationAction::Create(create),
+            Action::Update(update) => EntryCreationAction::Update(update),
+            _ => panic!("StoreEntry ops can only be built for Create and Update actions"),
+        };
+        Op::StoreEntry(StoreEntry {
+            action: signed(record, action),
+            entry: record.entry().as_option().unwrap().clone(),
+        })
+    }
+
+    pub fn register_update(&self, record: &Record) -> Op {
+        let Action::Update(update) = record.action().clone() else {
+            panic!("RegisterUpdate ops can only be built for Update actions");
+        };
+        Op::RegisterUpdate(RegisterUpdate {
+            update: signed(record, update),
+            new_entry: record.entry().as_option().cloned(),
+        })
+    }
+
+    pub fn register_delete(&self, record: &Record) -> Op {
+        let Action::Delete(delete) = record.action().clone() else {
+            panic!("RegisterDelete ops can only be built for Delete actions");
+        };
+        Op::RegisterDelete(RegisterDelete {
+            delete: signed(record, delete),
+        })
+    }
+
+    pub fn register_create_link(&self, record: &Record) -> Op {
+        let Action::CreateLink(create_link) = record.action().clone() else {
+            panic!("RegisterCreateLink ops can only be built for CreateLink actions");
+        };
+        Op::RegisterCreateLink(RegisterCreateLink {
+            create_link: signed(record, create_link),
+        })
+    }
+
+    pub fn register_delete_link(&self, record: &Record) -> Op {
+        let Action::DeleteLink(delete_link) = record.action().clone() else {
+            panic!("RegisterDeleteLink ops can only be built for DeleteLink actions");
+        };
+        let create_link =
+            match self.dht.lock().unwrap().records[&delete_link.link_add_address].action() {
+                Action::CreateLink(create_link) => create_link.clone(),
+                _ => panic!("DeleteLink must delete a CreateLink"),
+            };
+        Op::RegisterDeleteLink(RegisterDeleteLink {
+            delete_link: signed(record, delete_link),
+            create_link,
+        })
+    }
+
+    pub fn register_agent_activity(&self, record: &Record) -> Op {
+        Op::RegisterAgentActivity(RegisterAgentActivity {
+            action: record.signed_action.clone(),
+            cached_entry: None,
+        })
+    }
+
+    pub fn ops_for(&self, record: &Record) -> Vec<Op> {
+        let mut ops = vec![
+            self.store_record(record),
+            self.register_agent_activity(record),
+        ];
+        match record.action() {
+            Action::Create(_) => ops.push(self.store_entry(record)),
+            Action::Update(_) => {
+                ops.push(self.store_entry(record));
+                ops.push(self.register_update(record));
+            }
+            Action::Delete(_) => ops.push(self.register_delete(record)),
+            Action::CreateLink(_) => ops.push(self.register_create_link(record)),
+            Action::DeleteLink(_) => ops.push(self.register_delete_link(record)),
+            _ => {}
+        }
+        ops
+    }
+
+    fn next_raw_hash(&mut self) -> Vec<u8> {
+        self.next_hash += 1;
+        let mut raw = self.next_hash.to_be_bytes().to_vec();
+        raw.resize(36, 0);
+        raw
+    }
+
+    fn tick(&mut self) -> Timestamp {
+        self.wait(Duration::from_millis(1));
+        self.now
+    }
+
+    fn chain_head(&self, author: &AgentPubKey) -> (u32, ActionHash) {
+        let dht = self.dht.lock().unwrap();
+        let head = dht.chains[author].last().unwrap();
+        (
+            head.action().action_seq() + 1,
+            head.action_address().clone(),
+        )
+    }
+
+    fn entry_hash(&mut self, entry: &Entry) -> EntryHash {
+        let existing = self.dht.lock().unwrap().find_entry_hash(entry);
+        existing.unwrap_or_else(|| EntryHash::from_raw_36(self.next_raw_hash()))
+    }
+
+    fn commit(&mut self, action: Action, entry: Option<Entry>) -> Record {
+        let action_hash = ActionHash::from_raw_36(self.next_raw_hash());
+        let author = action.author().clone();
+        let signed_action = SignedActionHashed::with_presigned(
+            ActionHashed::with_pre_hashed(action, action_hash.clone()),
+            Signature([0; 64]),
+        );
+        let record = Record::new(signed_action.clone(), entry.clone());
+        let mut dht = self.dht.lock().unwrap();
+        if let (Some(entry), Some(entry_hash)) = (entry, record.action().entry_hash()) {
+            dht.entries.insert(entry_hash.clone(), entry);
+        }
+        dht.records.insert(action_hash, record.clone());
+        dht.chains.entry(author).or_default().push(signed_action);
+        record
+    }
+}
+
+/// Runs the integrity zome's `validate` callback, turning the short circuit that the host uses
+/// for missing dependencies back into the result the conductor would record.
+pub fn run_validate(op: Op) -> ExternResult<ValidateCallbackResult> {
+    match validate(op) {
+        Err(WasmError {
+            error: WasmErrorInner::HostShortCircuit(bytes),
+            ..
+        }) => Ok(ValidateCallbackResult::UnresolvedDependencies(
+            ExternIO(bytes).decode().map_err(|e| wasm_error!(e))?,
+        )),
+        result => result,
+    }
+}
+
+pub fn assert_valid(op: Op) {
+    assert_eq!(run_validate(op).unwrap(), ValidateCallbackResult::Valid);
+}
+
+pub fn assert_invalid(op: Op, reason: &str) {
+    assert_eq!(
+        run_validate(op).unwrap(),
+        ValidateCallbackResult::Invalid(reason.to_string())
+    );
+}
+
+pub fn assert_unresolved(op: Op, hash: impl Into<AnyDhtHash>) {
+    assert_eq!(
+        run_validate(op).unwrap(),
+        ValidateCallbackResult::UnresolvedDependencies(UnresolvedDependencies::Hashes(vec![
+            hash.into()
+        ]))
+    );
+}
+
+pub fn sample_post() -> Post {
+    Post {
+        title: "Lorem ipsum dolor sit amet".to_string(),
+        needs: vec!["Lorem ipsum".to_string()],
+        body: String::new(),
+        format: ContentFormat::Plain,
+        attachments: vec![],
+    }
+}
+
+fn signed<T: HashableContent>(record: &Record, content: T) -> SignedHashed<T> {
+    SignedHashed::with_presigned(
+        HoloHashed::with_pre_hashed(content, record.action_address().clone()),
+        record.signature().clone(),
+    )
+}
+
+fn app_entry_type(app_entry: &EntryTypes) -> EntryType {
+    let ScopedEntryDefIndex {
+        zome_index,
+        zome_type,
+    } = ScopedEntryDefIndex::try_from(app_entry).unwrap();
+    EntryType::App(AppEntryDef::new(
+        zome_type,
+        zome_index,
+        EntryVisibility::from(app_entry),
+    ))
+}
+
+fn short_circuit(dependencies: UnresolvedDependencies) -> WasmError {
+    wasm_error!(WasmErrorInner::HostShortCircuit(
+        ExternIO::encode(dependencies).unwrap().0
+    ))
+}
+
+fn unresolved(hash: impl Into<AnyDhtHash>) -> WasmError {
+    short_circuit(UnresolvedDependencies::Hashes(vec![hash.into()]))
+}
+
+fn posts_zome_info() -> ZomeInfo {
+    ZomeInfo {
+        name: "posts_integrity".into(),
+        id: ZomeIndex(0),
+        properties: SerializedBytes::default(),
+        entry_defs: EntryDefs(vec![]),
+        extern_fns: vec![],
+        zome_types: ScopedZomeTypesSet {
+            entries: ScopedZomeTypes(vec![(
+                ZomeIndex(0),
+                (0..UnitEntryTypes::ENUM_LEN).map(EntryDefIndex).collect(),
+            )]),
+            links: ScopedZomeTypes(vec![(
+                ZomeIndex(0),
+                (0..LinkTypes::ENUM_LEN).map(LinkType).collect(),
+            )]),
+        },
+    }
+}
+
+fn posts_dna_info(hash: DnaHash, properties: &PostsProperties) -> DnaInfo {
+    DnaInfo {
+        name: "posts_test".to_string(),
+        hash,
+        modifiers: DnaModifiers {
+            network_seed: String::new(),
+            properties: SerializedBytes::try_from(properties.clone()).unwrap(),
+            origin_time: Timestamp::from_micros(0),
+            quantum_time: Duration::from_secs(5 * 60),
+        },
+        zome_names: vec!["posts_integrity".into(), "file_storage_integrity".into()],
+    }
+}
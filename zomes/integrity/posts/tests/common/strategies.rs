@@ -0,0 +1,231 @@
+use hdi::prelude::*;
+use proptest::prelude::*;
+
+use posts_integrity::*;
+
+use super::{sample_post, TestDht};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Kind {
+    Post,
+    Comment,
+    Like,
+    Certificate,
+}
+
+// A reference from one entry to another, resolved against the kind of entry that the field
+// referencing it expects
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Reference {
+    Expected,
+    Dangling,
+    WrongType(Kind),
+}
+
+#[derive(Debug, Clone)]
+pub enum ArbitraryEntry {
+    Post(Post),
+    Comment {
+        post: Reference,
+    },
+    Like {
+        like: Option<Reference>,
+    },
+    Certificate {
+        post: Reference,
+        certifications: Vec<Reference>,
+        certificate_type: CertificateType,
+    },
+}
+
+impl Reference {
+    fn commit(&self, dht: &mut TestDht, author: &AgentPubKey, expected: Kind) -> Option<Record> {
+        let kind = match self {
+            Reference::Expected => expected,
+            Reference::Dangling => return None,
+            Reference::WrongType(kind) => *kind,
+        };
+        let app_entry = match kind {
+            Kind::Post => EntryTypes::Post(sample_post()),
+            Kind::Comment => {
+                let post = dht.create(author, EntryTypes::Post(sample_post()));
+                EntryTypes::Comment(Comment {
+                    post_hash: post.action_address().clone(),
+                })
+            }
+            Kind::Like => EntryTypes::Like(Like {
+                like_hash: None,
+                agent: author.clone(),
+            }),
+            Kind::Certificate => {
+                let post = dht.create(author, EntryTypes::Post(sample_post()));
+                EntryTypes::Certificate(Certificate {
+                    post_hash: post.action_address().clone(),
+                    agent: author.clone(),
+                    certifications_hashes: vec![],
+                    certificate_type: CertificateType::TypeOne,
+                    dna_hash: dht.dna_hash(),
+                })
+            }
+        };
+        Some(dht.create(author, app_entry))
+    }
+
+    fn action_hash(&self, dht: &mut TestDht, author: &AgentPubKey, expected: Kind) -> ActionHash {
+        match self.commit(dht, author, expected) {
+            Some(record) => record.action_address().clone(),
+            None => dht.fresh_action_hash(),
+        }
+    }
+
+    fn entry_hash(&self, dht: &mut TestDht, author: &AgentPubKey, expected: Kind) -> EntryHash {
+        match self.commit(dht, author, expected) {
+            Some(record) => record.action().entry_hash().unwrap().clone(),
+            None => dht.fresh_entry_hash(),
+        }
+    }
+}
+
+impl ArbitraryEntry {
+    pub fn references(&self) -> Vec<Reference> {
+        match self {
+            ArbitraryEntry::Post(_) => vec![],
+            ArbitraryEntry::Comment { post } => vec![*post],
+            ArbitraryEntry::Like { like } => like.iter().copied().collect(),
+            ArbitraryEntry::Certificate {
+                post,
+                certifications,
+                ..
+            } => std::iter::once(*post)
+                .chain(certifications.iter().copied())
+                .collect(),
+        }
+    }
+
+    // Commits the entries this one references, and then the entry itself
+    pub fn commit(&self, dht: &mut TestDht, author: &AgentPubKey) -> Record {
+        let app_entry = match self {
+            ArbitraryEntry::Post(post) => EntryTypes::Post(post.clone()),
+            ArbitraryEntry::Comment { post } => EntryTypes::Comment(Comment {
+                post_hash: post.action_hash(dht, author, Kind::Post),
+            }),
+            ArbitraryEntry::Like { like } => EntryTypes::Like(Like {
+                like_hash: like.map(|like| like.action_hash(dht, author, Kind::Like)),
+                agent: author.clone(),
+            }),
+            ArbitraryEntry::Certificate {
+                post,
+                certifications,
+                certificate_type,
+            } => EntryTypes::Certificate(Certificate {
+                post_hash: post.action_hash(dht, author, Kind::Post),
+                agent: author.clone(),
+                certifications_hashes: certifications
+                    .iter()
+                    .map(|certification| certification.entry_hash(dht, author, Kind::Certificate))
+                    .collect(),
+                certificate_type: certificate_type.clone(),
+                dna_hash: dht.dna_hash(),
+            }),
+        };
+        dht.create(author, app_entry)
+    }
+}
+
+pub fn any_post() -> BoxedStrategy<Post> {
+    (
+        ".{0,220}",
+        prop::collection::vec(".{0,220}", 0..25),
+        ".{0,300}",
+        content_format(),
+    )
+        .prop_map(|(title, needs, body, format)| Post {
+            title,
+            needs,
+            body,
+            format,
+            attachments: vec![],
+        })
+        .boxed()
+}
+
+pub fn valid_post() -> BoxedStrategy<Post> {
+    (
+        "[a-zA-Z0-9 ]{1,200}",
+        prop::collection::vec("[a-zA-Z0-9 ]{0,200}", 0..20),
+        "[a-zA-Z0-9 ]{0,500}",
+        content_format(),
+    )
+        .prop_map(|(title, needs, body, format)| Post {
+            title,
+            needs,
+            body,
+            format,
+            attachments: vec![],
+        })
+        .boxed()
+}
+
+pub fn content_format() -> BoxedStrategy<ContentFormat> {
+    prop_oneof![Just(ContentFormat::Plain), Just(ContentFormat::Markdown)].boxed()
+}
+
+pub fn kind() -> BoxedStrategy<Kind> {
+    prop_oneof![
+        Just(Kind::Post),
+        Just(Kind::Comment),
+        Just(Kind::Like),
+        Just(Kind::Certificate),
+    ]
+    .boxed()
+}
+
+pub fn any_reference() -> BoxedStrategy<Reference> {
+    prop_oneof![
+        Just(Reference::Expected),
+        Just(Reference::Dangling),
+        kind().prop_map(Reference::WrongType),
+    ]
+    .boxed()
+}
+
+pub fn certificate_type() -> BoxedStrategy<CertificateType> {
+    prop_oneof![
+        Just(CertificateType::TypeOne),
+        Just(CertificateType::TypeTwo)
+    ]
+    .boxed()
+}
+
+pub fn entry_with_references(reference: BoxedStrategy<Reference>) -> BoxedStrategy<ArbitraryEntry> {
+    prop_oneof![
+        reference
+            .clone()
+            .prop_map(|post| ArbitraryEntry::Comment { post }),
+        prop::option::of(reference.clone()).prop_map(|like| ArbitraryEntry::Like { like }),
+        (
+            reference.clone(),
+            prop::collection::vec(reference, 0..3),
+            certificate_type(),
+        )
+            .prop_map(|(post, certifications, certificate_type)| {
+                ArbitraryEntry::Certificate {
+                    post,
+                    certifications,
+                    certificate_type,
+                }
+            }),
+    ]
+    .boxed()
+}
+
+pub fn entry(
+    post: BoxedStrategy<Post>,
+    reference: BoxedStrategy<Reference>,
+) -> BoxedStrategy<ArbitraryEntry> {
+    prop_oneof![
+        post.prop_map(ArbitraryEntry::Post),
+        entry_with_references(reference),
+    ]
+    .boxed()
+}
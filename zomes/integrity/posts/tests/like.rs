@@ -0,0 +1,220 @@
+use hdi::prelude::*;
+
+use posts_integrity::*;
+
+mod common;
+use common::*;
+
+fn like(dht: &mut TestDht, author: &AgentPubKey, like_hash: Option<ActionHash>) -> Record {
+    let agent = author.clone();
+    dht.create(author, EntryTypes::Like(Like { like_hash, agent }))
+}
+
+#[test]
+fn create_like_is_valid() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+
+    let record = like(&mut dht, &alice, None);
+
+    for op in dht.ops_for(&record) {
+        assert_valid(op);
+    }
+}
+
+#[test]
+fn create_like_of_a_like_is_valid() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+    let bob = dht.agent();
+
+    let liked = like(&mut dht, &alice, None);
+    let record = like(&mut dht, &bob, Some(liked.action_address().clone()));
+
+    for op in dht.ops_for(&record) {
+        assert_valid(op);
+    }
+}
+
+#[test]
+fn create_like_of_missing_like_is_unresolved() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+
+    let missing = dht.fresh_action_hash();
+    let record = like(&mut dht, &alice, Some(missing.clone()));
+
+    assert_unresolved(dht.store_entry(&record), missing.clone());
+    assert_unresolved(dht.store_record(&record), missing);
+}
+
+#[test]
+fn create_like_of_a_post_is_rejected() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+
+    let post = dht.create(&alice, EntryTypes::Post(sample_post()));
+    let record = like(&mut dht, &alice, Some(post.action_address().clone()));
+
+    assert_invalid(dht.store_record(&record), "Likes can only reference other Likes");
+}
+
+#[test]
+fn update_like_is_invalid() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+
+    let original = like(&mut dht, &alice, None);
+    let record = dht.update(
+        &alice,
+        &original,
+        EntryTypes::Like(Like {
+            like_hash: None,
+            agent: alice.clone(),
+        }),
+    );
+
+    assert_invalid(dht.store_record(&record), "Likes cannot be updated");
+}
+
+#[test]
+fn delete_like_is_valid() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+
+    let original = like(&mut dht, &alice, None);
+    let record = dht.delete(&alice, &original);
+
+    for op in dht.ops_for(&record) {
+        assert_valid(op);
+    }
+}
+
+#[test]
+fn like_to_likes_link_is_valid() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+    let bob = dht.agent();
+
+    let liked = like(&mut dht, &alice, None);
+    let liking = like(&mut dht, &bob, Some(liked.action_address().clone()));
+    let record = dht.create_link(
+        &bob,
+        liked.action_address().clone(),
+        liking.action_address().clone(),
+        LinkTypes::LikeToLikes,
+    );
+
+    for op in dht.ops_for(&record) {
+        assert_valid(op);
+    }
+
+    let record = dht.delete_link(&bob, &record);
+
+    for op in dht.ops_for(&record) {
+        assert_valid(op);
+    }
+}
+
+#[test]
+fn like_to_likes_link_to_missing_like_is_unresolved() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+
+    let liked = like(&mut dht, &alice, None);
+    let missing = dht.fresh_action_hash();
+    let record = dht.create_link(
+        &alice,
+        liked.action_address().clone(),
+        missing.clone(),
+        LinkTypes::LikeToLikes,
+    );
+
+    assert_unresolved(dht.register_create_link(&record), missing);
+}
+
+#[test]
+fn create_like_of_a_certificate_is_invalid() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+
+    let post = dht.create(&alice, EntryTypes::Post(sample_post()));
+    let certificate = dht.create(
+        &alice,
+        EntryTypes::Certificate(Certificate {
+            post_hash: post.action_address().clone(),
+            agent: alice.clone(),
+            certifications_hashes: vec![],
+            certificate_type: CertificateType::TypeOne,
+            dna_hash: dht.dna_hash(),
+        }),
+    );
+    let record = like(&mut dht, &alice, Some(certificate.action_address().clone()));
+
+    assert_invalid(
+        dht.store_record(&record),
+        "Likes can only reference other Likes",
+    );
+}
+
+#[test]
+fn agent_to_likes_link_is_valid() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+    let bob = dht.agent();
+
+    let liking = dht.create(
+        &bob,
+        EntryTypes::Like(Like {
+            like_hash: None,
+            agent: alice.clone(),
+        }),
+    );
+    let record = dht.create_link(
+        &bob,
+        alice.clone(),
+        liking.action_address().clone(),
+        LinkTypes::AgentToLikes,
+    );
+
+    for op in dht.ops_for(&record) {
+        assert_valid(op);
+    }
+}
+
+#[test]
+fn agent_to_likes_link_must_start_from_the_liked_agent() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+    let bob = dht.agent();
+    let carol = dht.agent();
+
+    let liking = dht.create(
+        &bob,
+        EntryTypes::Like(Like {
+            like_hash: None,
+            agent: alice.clone(),
+        }),
+    );
+    let wrong_base = dht.create_link(
+        &bob,
+        carol.clone(),
+        liking.action_address().clone(),
+        LinkTypes::AgentToLikes,
+    );
+    let wrong_author = dht.create_link(
+        &carol,
+        alice.clone(),
+        liking.action_address().clone(),
+        LinkTypes::AgentToLikes,
+    );
+
+    assert_invalid(
+        dht.store_record(&wrong_base),
+        "AgentToLikes links must start from the liked agent",
+    );
+    assert_invalid(
+        dht.store_record(&wrong_author),
+        "Only the author of a Like can link it to the liked agent",
+    );
+}
@@ -0,0 +1,293 @@
+use hdi::prelude::*;
+
+use posts_integrity::*;
+
+mod common;
+use common::*;
+
+fn previous_dna_hash() -> DnaHash {
+    DnaHash::from_raw_36(vec![1; 36])
+}
+
+fn migrated_dht() -> TestDht {
+    TestDht::with_properties(PostsProperties {
+        previous_dna_hash: Some(previous_dna_hash()),
+        ..Default::default()
+    })
+}
+
+#[test]
+fn migration_pointer_to_the_declared_previous_dna_is_valid() {
+    let mut dht = migrated_dht();
+    let alice = dht.agent();
+
+    let record = dht.create(
+        &alice,
+        EntryTypes::MigrationPointer(MigrationPointer {
+            previous_dna_hash: previous_dna_hash(),
+        }),
+    );
+
+    for op in dht.ops_for(&record) {
+        assert_valid(op);
+    }
+
+    let path_hash = dht.fresh_entry_hash();
+    let link = dht.create_link(
+        &alice,
+        path_hash,
+        record.action_address().clone(),
+        LinkTypes::MigrationPointers,
+    );
+
+    for op in dht.ops_for(&link) {
+        assert_valid(op);
+    }
+}
+
+#[test]
+fn migration_pointer_to_another_dna_is_invalid() {
+    let reason = "MigrationPointer must point to the previous DNA declared in the DNA properties";
+
+    let mut dht = migrated_dht();
+    let alice = dht.agent();
+    let record = dht.create(
+        &alice,
+        EntryTypes::MigrationPointer(MigrationPointer {
+            previous_dna_hash: DnaHash::from_raw_36(vec![2; 36]),
+        }),
+    );
+    assert_invalid(dht.store_record(&record), reason);
+
+    // Nor can a DNA without a previous version point anywhere
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+    let record = dht.create(
+        &alice,
+        EntryTypes::MigrationPointer(MigrationPointer {
+            previous_dna_hash: previous_dna_hash(),
+        }),
+    );
+    assert_invalid(dht.store_record(&record), reason);
+}
+
+#[test]
+fn migration_pointer_cannot_be_updated_or_deleted() {
+    let mut dht = migrated_dht();
+    let alice = dht.agent();
+
+    let original = dht.create(
+        &alice,
+        EntryTypes::MigrationPointer(MigrationPointer {
+            previous_dna_hash: previous_dna_hash(),
+        }),
+    );
+    let update = dht.update(
+        &alice,
+        &original,
+        EntryTypes::MigrationPointer(MigrationPointer {
+            previous_dna_hash: previous_dna_hash(),
+        }),
+    );
+    assert_invalid(dht.store_record(&update), "MigrationPointers cannot be updated");
+
+    let delete = dht.delete(&alice, &original);
+    assert_invalid(dht.store_record(&delete), "MigrationPointers cannot be deleted");
+}
+
+fn signed_action_tag(signed_action: SignedActionHashed) -> LinkTag {
+    LinkTag::new(ExternIO::encode(signed_action).unwrap().0)
+}
+
+// The previous DNA's Post is committed to the same TestDht, since the harness has one DHT only
+fn previous_post_and_copy(dht: &mut TestDht, author: &AgentPubKey) -> (Record, Record) {
+    let previous_post = dht.create(author, EntryTypes::Post(sample_post()));
+    let copy = dht.create(author, EntryTypes::Post(sample_post()));
+    (previous_post, copy)
+}
+
+#[test]
+fn migrated_posts_link_to_a_faithful_copy_is_valid() {
+    let mut dht = migrated_dht();
+    let alice = dht.agent();
+
+    let (previous_post, copy) = previous_post_and_copy(&mut dht, &alice);
+    let link = dht.create_link_with_tag(
+        &alice,
+        previous_post.action_address().clone(),
+        copy.action_address().clone(),
+        LinkTypes::MigratedPosts,
+        signed_action_tag(previous_post.signed_action.clone()),
+    );
+
+    for op in dht.ops_for(&link) {
+        assert_valid(op);
+    }
+}
+
+// Posts are migrated because their schema changed, so a previous Post committed in an older
+// shape matches its copy in the current one
+#[test]
+fn migrated_posts_link_to_a_copy_of_an_older_shape_is_valid() {
+    let mut dht = migrated_dht();
+    let alice = dht.agent();
+
+    let v1_entry = Entry::App(AppEntryBytes(SerializedBytes::from(UnsafeBytes::from(
+        ExternIO::encode(PostV1 {
+            title: sample_post().title,
+            needs: sample_post().needs,
+        })
+        .unwrap()
+        .0,
+    ))));
+    let previous_post = dht.create_raw(
+        &alice,
+        UnitEntryTypes::Post.try_into().unwrap(),
+        v1_entry,
+    );
+    let copy = dht.create(&alice, EntryTypes::Post(sample_post()));
+    assert_ne!(
+        previous_post.action().entry_hash(),
+        copy.action().entry_hash()
+    );
+    let link = dht.create_link_with_tag(
+        &alice,
+        previous_post.action_address().clone(),
+        copy.action_address().clone(),
+        LinkTypes::MigratedPosts,
+        signed_action_tag(previous_post.signed_action.clone()),
+    );
+
+    for op in dht.ops_for(&link) {
+        assert_valid(op);
+    }
+
+    // But a copy that added a body is not the same Post
+    let copy_with_body = dht.create(
+        &alice,
+        EntryTypes::Post(Post {
+            body: "A body".to_string(),
+            ..sample_post()
+        }),
+    );
+    let link = dht.create_link_with_tag(
+        &alice,
+        previous_post.action_address().clone(),
+        copy_with_body.action_address().clone(),
+        LinkTypes::MigratedPosts,
+        signed_action_tag(previous_post.signed_action.clone()),
+    );
+    assert_invalid(
+        dht.store_record(&link),
+        "A migrated Post must have the same content as the previous Post",
+    );
+}
+
+#[test]
+fn migrated_posts_link_must_point_to_a_post() {
+    let mut dht = migrated_dht();
+    let alice = dht.agent();
+
+    let (previous_post, copy) = previous_post_and_copy(&mut dht, &alice);
+    let comment = dht.create(
+        &alice,
+        EntryTypes::Comment(Comment {
+            post_hash: copy.action_address().clone(),
+        }),
+    );
+    let link = dht.create_link_with_tag(
+        &alice,
+        previous_post.action_address().clone(),
+        comment.action_address().clone(),
+        LinkTypes::MigratedPosts,
+        signed_action_tag(previous_post.signed_action.clone()),
+    );
+
+    assert_invalid(
+        dht.register_create_link(&link),
+        "MigratedPosts links must point to a Post",
+    );
+
+    let delete_link = dht.delete_link(&alice, &link);
+    assert_invalid(
+        dht.register_delete_link(&delete_link),
+        "MigratedPosts links cannot be deleted",
+    );
+}
+
+#[test]
+fn only_the_author_can_migrate_a_post() {
+    let mut dht = migrated_dht();
+    let alice = dht.agent();
+    let bob = dht.agent();
+
+    let previous_post = dht.create(&alice, EntryTypes::Post(sample_post()));
+    let copy = dht.create(&bob, EntryTypes::Post(sample_post()));
+    let link = dht.create_link_with_tag(
+        &bob,
+        previous_post.action_address().clone(),
+        copy.action_address().clone(),
+        LinkTypes::MigratedPosts,
+        signed_action_tag(previous_post.signed_action.clone()),
+    );
+
+    assert_invalid(
+        dht.store_record(&link),
+        "Only the author of a Post can migrate it",
+    );
+}
+
+#[test]
+fn migrated_post_must_match_the_previous_post() {
+    let mut dht = migrated_dht();
+    let alice = dht.agent();
+
+    let previous_post = dht.create(&alice, EntryTypes::Post(sample_post()));
+    let copy = dht.create(
+        &alice,
+        EntryTypes::Post(Post {
+            title: "Something else".to_string(),
+            ..sample_post()
+        }),
+    );
+    let link = dht.create_link_with_tag(
+        &alice,
+        previous_post.action_address().clone(),
+        copy.action_address().clone(),
+        LinkTypes::MigratedPosts,
+        signed_action_tag(previous_post.signed_action.clone()),
+    );
+
+    assert_invalid(
+        dht.store_record(&link),
+        "A migrated Post must have the same content as the previous Post",
+    );
+}
+
+#[test]
+fn migrated_posts_link_must_carry_the_signed_previous_post() {
+    let mut dht = migrated_dht();
+    let alice = dht.agent();
+
+    let (previous_post, copy) = previous_post_and_copy(&mut dht, &alice);
+    let other_post = dht.create(&alice, EntryTypes::Post(sample_post()));
+    let forged = SignedActionHashed::with_presigned(
+        previous_post.signed_action.hashed.clone(),
+        Signature([1; 64]),
+    );
+
+    let reason = "MigratedPosts links must carry the signed action of the previous Post";
+    for tag in [
+        signed_action_tag(forged),
+        signed_action_tag(other_post.signed_action.clone()),
+    ] {
+        let link = dht.create_link_with_tag(
+            &alice,
+            previous_post.action_address().clone(),
+            copy.action_address().clone(),
+            LinkTypes::MigratedPosts,
+            tag,
+        );
+        assert_invalid(dht.store_record(&link), reason);
+    }
+}
@@ -0,0 +1,451 @@
+use std::time::Duration;
+
+use hdi::prelude::*;
+
+use posts_integrity::*;
+
+mod common;
+use common::*;
+
+#[test]
+fn create_post_is_valid() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+
+    let record = dht.create(&alice, EntryTypes::Post(sample_post()));
+
+    for op in dht.ops_for(&record) {
+        assert_valid(op);
+    }
+}
+
+#[test]
+fn create_post_with_empty_title_is_invalid() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+
+    let record = dht.create(
+        &alice,
+        EntryTypes::Post(Post {
+            title: String::new(),
+            needs: vec![],
+            body: String::new(),
+            format: ContentFormat::Plain,
+            attachments: vec![],
+        }),
+    );
+
+    let reason = "Post title is 0 characters long, but must be between 1 and 200 characters long";
+    assert_invalid(dht.store_entry(&record), reason);
+    assert_invalid(dht.store_record(&record), reason);
+}
+
+#[test]
+fn create_post_respects_configured_limits() {
+    let mut dht = TestDht::with_properties(PostsProperties {
+        post_limits: PostLimits {
+            max_needs: 1,
+            ..Default::default()
+        },
+        ..Default::default()
+    });
+    let alice = dht.agent();
+
+    let record = dht.create(
+        &alice,
+        EntryTypes::Post(Post {
+            title: "A title".to_string(),
+            needs: vec!["A need".to_string(), "Another need".to_string()],
+            body: String::new(),
+            format: ContentFormat::Plain,
+            attachments: vec![],
+        }),
+    );
+
+    assert_invalid(
+        dht.store_record(&record),
+        "Post has 2 needs, but at most 1 are allowed",
+    );
+}
+
+#[test]
+fn create_post_with_file_manifest_attachments_is_valid() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+
+    let manifest = dht.create_file_manifest(
+        &alice,
+        file_storage_types::FileManifest {
+            name: "notes.txt".to_string(),
+            mime_type: "text/plain".to_string(),
+            size: 0,
+            chunks: vec![],
+        },
+    );
+    let record = dht.create(
+        &alice,
+        EntryTypes::Post(Post {
+            body: "# A body\n\nWith an attachment".to_string(),
+            format: ContentFormat::Markdown,
+            attachments: vec![manifest.action_address().clone()],
+            ..sample_post()
+        }),
+    );
+
+    for op in dht.ops_for(&record) {
+        assert_valid(op);
+    }
+}
+
+#[test]
+fn create_post_with_attachments_that_are_not_file_manifests_is_invalid() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+
+    let attached = dht.create(&alice, EntryTypes::Post(sample_post()));
+    let record = dht.create(
+        &alice,
+        EntryTypes::Post(Post {
+            body: "# A body\n\nWith an attachment".to_string(),
+            format: ContentFormat::Markdown,
+            attachments: vec![attached.action_address().clone()],
+            ..sample_post()
+        }),
+    );
+
+    let reason = "Post attachments must be FileManifests";
+    assert_invalid(dht.store_entry(&record), reason);
+    assert_invalid(dht.store_record(&record), reason);
+}
+
+#[test]
+fn create_post_with_missing_attachment_is_unresolved() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+
+    let missing = dht.fresh_action_hash();
+    let record = dht.create(
+        &alice,
+        EntryTypes::Post(Post {
+            attachments: vec![missing.clone()],
+            ..sample_post()
+        }),
+    );
+
+    assert_unresolved(dht.store_entry(&record), missing.clone());
+    assert_unresolved(dht.store_record(&record), missing);
+}
+
+#[test]
+fn create_post_respects_rate_limit() {
+    let mut dht = TestDht::with_properties(PostsProperties {
+        rate_limit: Some(RateLimit {
+            max_creates: 2,
+            window_secs: 60,
+        }),
+        ..Default::default()
+    });
+    let alice = dht.agent();
+
+    dht.create(&alice, EntryTypes::Post(sample_post()));
+    dht.create(&alice, EntryTypes::Post(sample_post()));
+    let record = dht.create(&alice, EntryTypes::Post(sample_post()));
+
+    assert_invalid(
+        dht.store_record(&record),
+        "Rate limit exceeded: at most 2 posts and comments can be created every 60 seconds",
+    );
+
+    dht.wait(Duration::from_secs(61));
+    let record = dht.create(&alice, EntryTypes::Post(sample_post()));
+
+    assert_valid(dht.store_record(&record));
+}
+
+#[test]
+fn rate_limit_counts_posts_beyond_the_first_page_of_activity() {
+    let mut dht = TestDht::with_properties(PostsProperties {
+        rate_limit: Some(RateLimit {
+            max_creates: 1,
+            window_secs: 60,
+        }),
+        ..Default::default()
+    });
+    let alice = dht.agent();
+
+    let post = dht.create(&alice, EntryTypes::Post(sample_post()));
+    for _ in 0..10 {
+        let base = dht.fresh_entry_hash();
+        dht.create_link(
+            &alice,
+            base,
+            post.action_address().clone(),
+            LinkTypes::AllPosts,
+        );
+    }
+    let record = dht.create(&alice, EntryTypes::Post(sample_post()));
+
+    assert_invalid(
+        dht.store_record(&record),
+        "Rate limit exceeded: at most 1 posts and comments can be created every 60 seconds",
+    );
+}
+
+#[test]
+fn update_post_is_valid() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+
+    let original = dht.create(&alice, EntryTypes::Post(sample_post()));
+    let record = dht.update(
+        &alice,
+        &original,
+        EntryTypes::Post(Post {
+            title: "Updated title".to_string(),
+            needs: vec![],
+            body: String::new(),
+            format: ContentFormat::Plain,
+            attachments: vec![],
+        }),
+    );
+
+    for op in dht.ops_for(&record) {
+        assert_valid(op);
+    }
+}
+
+#[test]
+fn update_post_with_invalid_fields_is_invalid() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+
+    let original = dht.create(&alice, EntryTypes::Post(sample_post()));
+    let record = dht.update(
+        &alice,
+        &original,
+        EntryTypes::Post(Post {
+            title: "Tab\tseparated".to_string(),
+            needs: vec![],
+            body: String::new(),
+            format: ContentFormat::Plain,
+            attachments: vec![],
+        }),
+    );
+
+    assert_invalid(
+        dht.store_record(&record),
+        "Post title contains the disallowed character '\\t'",
+    );
+}
+
+#[test]
+fn update_of_missing_post_is_unresolved() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+
+    let original = dht.create(&alice, EntryTypes::Post(sample_post()));
+    let record = dht.update(&alice, &original, EntryTypes::Post(sample_post()));
+    dht.forget(&original);
+
+    assert_unresolved(dht.store_record(&record), original.action_address().clone());
+}
+
+#[test]
+fn delete_post_is_valid() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+
+    let original = dht.create(&alice, EntryTypes::Post(sample_post()));
+    let record = dht.delete(&alice, &original);
+
+    for op in dht.ops_for(&record) {
+        assert_valid(op);
+    }
+}
+
+#[test]
+fn post_updates_link_is_valid() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+
+    let original = dht.create(&alice, EntryTypes::Post(sample_post()));
+    let update = dht.update(&alice, &original, EntryTypes::Post(sample_post()));
+    let record = dht.create_link(
+        &alice,
+        original.action_address().clone(),
+        update.action_address().clone(),
+        LinkTypes::PostUpdates,
+    );
+
+    for op in dht.ops_for(&record) {
+        assert_valid(op);
+    }
+}
+
+#[test]
+fn post_updates_link_to_missing_post_is_unresolved() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+
+    let original = dht.create(&alice, EntryTypes::Post(sample_post()));
+    let missing = dht.fresh_action_hash();
+    let record = dht.create_link(
+        &alice,
+        original.action_address().clone(),
+        missing.clone(),
+        LinkTypes::PostUpdates,
+    );
+
+    assert_unresolved(dht.register_create_link(&record), missing.clone());
+    assert_unresolved(dht.store_record(&record), missing);
+}
+
+#[test]
+fn post_updates_link_cannot_be_deleted() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+
+    let original = dht.create(&alice, EntryTypes::Post(sample_post()));
+    let update = dht.update(&alice, &original, EntryTypes::Post(sample_post()));
+    let create_link = dht.create_link(
+        &alice,
+        original.action_address().clone(),
+        update.action_address().clone(),
+        LinkTypes::PostUpdates,
+    );
+    let record = dht.delete_link(&alice, &create_link);
+
+    let reason = "PostUpdates links cannot be deleted";
+    assert_invalid(dht.register_delete_link(&record), reason);
+    assert_invalid(dht.store_record(&record), reason);
+}
+
+#[test]
+fn all_posts_link_is_valid() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+
+    let post = dht.create(&alice, EntryTypes::Post(sample_post()));
+    let path_hash = dht.fresh_entry_hash();
+    let record = dht.create_link(
+        &alice,
+        path_hash,
+        post.action_address().clone(),
+        LinkTypes::AllPosts,
+    );
+
+    for op in dht.ops_for(&record) {
+        assert_valid(op);
+    }
+
+    let record = dht.delete_link(&alice, &record);
+
+    for op in dht.ops_for(&record) {
+        assert_valid(op);
+    }
+}
+
+#[test]
+fn all_posts_link_to_a_comment_is_rejected() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+
+    let post = dht.create(&alice, EntryTypes::Post(sample_post()));
+    let comment = dht.create(
+        &alice,
+        EntryTypes::Comment(Comment {
+            post_hash: post.action_address().clone(),
+        }),
+    );
+    let path_hash = dht.fresh_entry_hash();
+    let record = dht.create_link(
+        &alice,
+        path_hash,
+        comment.action_address().clone(),
+        LinkTypes::AllPosts,
+    );
+
+    assert!(run_validate(dht.register_create_link(&record)).is_err());
+}
+
+#[test]
+fn posts_by_author_link_is_valid() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+
+    let post = dht.create(&alice, EntryTypes::Post(sample_post()));
+    let record = dht.create_link(
+        &alice,
+        alice.clone(),
+        post.action_address().clone(),
+        LinkTypes::PostsByAuthor,
+    );
+
+    for op in dht.ops_for(&record) {
+        assert_valid(op);
+    }
+
+    let record = dht.delete_link(&alice, &record);
+
+    for op in dht.ops_for(&record) {
+        assert_valid(op);
+    }
+}
+
+#[test]
+fn entry_hash_links_are_valid() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+
+    let post = dht.create(&alice, EntryTypes::Post(sample_post()));
+    let entry_hash = post.action().entry_hash().unwrap().clone();
+    let path_hash = dht.fresh_entry_hash();
+    let all_posts = dht.create_link(
+        &alice,
+        path_hash,
+        entry_hash.clone(),
+        LinkTypes::AllPostsEntryHash,
+    );
+    let by_author = dht.create_link(
+        &alice,
+        alice.clone(),
+        entry_hash,
+        LinkTypes::PostsByAuthorEntryHash,
+    );
+
+    for record in [all_posts, by_author] {
+        for op in dht.ops_for(&record) {
+            assert_valid(op);
+        }
+        let record = dht.delete_link(&alice, &record);
+        for op in dht.ops_for(&record) {
+            assert_valid(op);
+        }
+    }
+}
+
+#[test]
+fn entry_hash_link_to_missing_entry_is_unresolved() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+
+    let missing = dht.fresh_entry_hash();
+    let record = dht.create_link(
+        &alice,
+        alice.clone(),
+        missing.clone(),
+        LinkTypes::PostsByAuthorEntryHash,
+    );
+
+    assert_unresolved(dht.register_create_link(&record), missing);
+}
+
+#[test]
+fn genesis_is_valid() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+
+    let create_agent = dht.chain_top(&alice);
+
+    assert_valid(dht.register_agent_activity(&create_agent));
+}
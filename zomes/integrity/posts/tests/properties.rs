@@ -0,0 +1,83 @@
+use hdi::prelude::*;
+use proptest::prelude::*;
+
+use posts_integrity::*;
+
+mod common;
+use common::strategies::*;
+use common::*;
+
+proptest! {
+    #[test]
+    fn validate_is_deterministic(entry in entry(any_post(), any_reference())) {
+        let mut dht = TestDht::new();
+        let alice = dht.agent();
+        let record = entry.commit(&mut dht, &alice);
+
+        for op in dht.ops_for(&record) {
+            let first = format!("{:?}", run_validate(op.clone()));
+            let second = format!("{:?}", run_validate(op));
+            prop_assert_eq!(first, second);
+        }
+    }
+
+    #[test]
+    fn entries_referencing_existing_entries_of_the_expected_type_are_valid(
+        entry in entry(valid_post(), Just(Reference::Expected).boxed())
+    ) {
+        let mut dht = TestDht::new();
+        let alice = dht.agent();
+        let record = entry.commit(&mut dht, &alice);
+
+        for op in dht.ops_for(&record) {
+            prop_assert_eq!(run_validate(op).unwrap(), ValidateCallbackResult::Valid);
+        }
+    }
+
+    #[test]
+    fn missing_references_are_unresolved_dependencies(
+        entry in entry_with_references(
+            prop_oneof![Just(Reference::Expected), Just(Reference::Dangling)].boxed()
+        )
+    ) {
+        prop_assume!(entry.references().contains(&Reference::Dangling));
+        let mut dht = TestDht::new();
+        let alice = dht.agent();
+        let record = entry.commit(&mut dht, &alice);
+
+        for op in [dht.store_entry(&record), dht.store_record(&record)] {
+            let result = run_validate(op);
+            prop_assert!(
+                matches!(result, Ok(ValidateCallbackResult::UnresolvedDependencies(_))),
+                "Expected unresolved dependencies, got {:?}",
+                result
+            );
+        }
+    }
+
+    #[test]
+    fn references_of_the_wrong_type_are_never_valid(
+        entry in entry_with_references(kind().prop_map(Reference::WrongType).boxed())
+    ) {
+        let expected_kinds = match &entry {
+            ArbitraryEntry::Comment { .. } => vec![Kind::Post],
+            ArbitraryEntry::Like { .. } => vec![Kind::Like],
+            ArbitraryEntry::Certificate { .. } => vec![Kind::Post, Kind::Certificate],
+            ArbitraryEntry::Post(_) => vec![],
+        };
+        prop_assume!(entry.references().iter().any(|reference| match reference {
+            Reference::WrongType(kind) => !expected_kinds.contains(kind),
+            _ => false,
+        }));
+        let mut dht = TestDht::new();
+        let alice = dht.agent();
+        let record = entry.commit(&mut dht, &alice);
+
+        let result = run_validate(dht.store_record(&record));
+        prop_assert!(
+            !matches!(result, Ok(ValidateCallbackResult::Valid)),
+            "Expected a rejection, got {:?}",
+            result
+        );
+    }
+}
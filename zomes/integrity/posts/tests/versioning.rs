@@ -0,0 +1,92 @@
+use hdi::prelude::*;
+
+use posts_integrity::*;
+
+mod common;
+use common::*;
+
+// Entries committed before versioning have the V1 shape, and must keep being read
+#[test]
+fn v1_entries_are_upcast_when_read() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+    let post_hash = dht.fresh_action_hash();
+
+    let post: Post = ExternIO::encode(PostV1 {
+        title: "A title".to_string(),
+        needs: vec!["A need".to_string()],
+    })
+    .unwrap()
+    .decode()
+    .unwrap();
+    assert_eq!(
+        post,
+        Post {
+            title: "A title".to_string(),
+            needs: vec!["A need".to_string()],
+            body: String::new(),
+            format: ContentFormat::Plain,
+            attachments: vec![],
+        }
+    );
+
+    let comment: Comment = ExternIO::encode(CommentV1 {
+        post_hash: post_hash.clone(),
+    })
+    .unwrap()
+    .decode()
+    .unwrap();
+    assert_eq!(comment.post_hash, post_hash);
+
+    let like: Like = ExternIO::encode(LikeV1 {
+        like_hash: None,
+        agent: alice.clone(),
+    })
+    .unwrap()
+    .decode()
+    .unwrap();
+    assert_eq!(
+        like,
+        Like {
+            like_hash: None,
+            agent: alice.clone(),
+        }
+    );
+
+    let certificate: Certificate = ExternIO::encode(CertificateV1 {
+        post_hash: post_hash.clone(),
+        agent: alice.clone(),
+        certifications_hashes: vec![],
+        certificate_type: CertificateType::TypeTwo,
+        dna_hash: dht.dna_hash(),
+    })
+    .unwrap()
+    .decode()
+    .unwrap();
+    assert_eq!(certificate.certificate_type, CertificateType::TypeTwo);
+    assert_eq!(certificate.dna_hash, dht.dna_hash());
+}
+
+#[test]
+fn entries_are_read_back_in_the_shape_they_were_committed_with() {
+    let post = sample_post();
+    let decoded: Post = ExternIO::encode(post.clone()).unwrap().decode().unwrap();
+
+    assert_eq!(decoded, post);
+}
+
+#[test]
+fn entries_of_another_type_are_not_read_as_any_version() {
+    let mut dht = TestDht::new();
+    let alice = dht.agent();
+
+    let result = ExternIO::encode(LikeV1 {
+        like_hash: None,
+        agent: alice,
+    })
+    .unwrap()
+    .decode::<Post>();
+
+    assert!(format!("{:?}", result.unwrap_err())
+        .contains("did not match any variant of untagged enum VersionedPost"));
+}